@@ -1,7 +1,6 @@
-#[cfg(not(test))]
-use std::env;
 #[cfg(test)]
 use std::thread;
+use std::env;
 use std::{
     fs,
     path::{Path, PathBuf},
@@ -40,67 +39,145 @@ const NODE_BINARY_NEXT_NAME: &str = "casper-node-next";
 #[cfg(not(test))]
 const DEFAULT_BINARY_DIR_OVERRIDE: &str = "CASPER_BIN_DIR";
 
+/// The name of the drop-in fragment directory searched for alongside the main launcher config.
+const CONFIG_FRAGMENTS_DIR_NAME: &str = "casper.d";
+
+/// Environment variable overriding `node_config_path`, applied after the on-disk file is read but
+/// before the CLI argument (the topmost layer).
+const NODE_CONFIG_PATH_ENV_VAR: &str = "CASPER_NODE_CONFIG_PATH";
+
+/// Identifies which configuration layer supplied a field's value, in increasing precedence.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ConfigSource {
+    Default,
+    File,
+    Env,
+    Cli,
+}
+
 /// This "leaks" the tempdir, in that it won't be removed after the tests finish running.  However,
 /// it is only ever used for testing very small files, and it makes the production code and test
 /// code simpler, so it's a worthwhile trade off.
 #[cfg(test)]
 static TEMP_DIR: Lazy<TempDir> = Lazy::new(|| tempfile::tempdir().expect("should create temp dir"));
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Debug)]
+/// Deep-merges `overlay` into `base`: tables merge key-by-key (recursing into nested tables),
+/// while scalars and arrays in `overlay` simply replace the corresponding value in `base`.
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, overlay_value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(base_value) => merge_toml(base_value, overlay_value),
+                    None => {
+                        base_table.insert(key, overlay_value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
     node_config_path: PathBuf,
+    /// The CLI `--node-config` argument this `Config` was originally constructed with, if any,
+    /// re-applied on each `reload` so the layer precedence doesn't silently change mid-run.
+    #[serde(skip)]
+    cli_override: Option<String>,
+}
+
+/// Equality only considers `node_config_path`: `cli_override` is bookkeeping for `reload` and
+/// shouldn't affect whether two configs are considered the same.
+impl PartialEq for Config {
+    fn eq(&self, other: &Self) -> bool {
+        self.node_config_path == other.node_config_path
+    }
 }
 
+impl Eq for Config {}
+
 impl Config {
-    /// If `maybe_node_config_path` is `Some`:
-    ///   * checks the node's config file exists at the given path (returns error if not)
-    ///   * writes the path value to the casper-node-launcher's config file (returns an error if it
-    ///     can't be written)
+    /// Constructs a new `Config`, applying each configuration layer in increasing precedence:
+    /// built-in defaults, the on-disk TOML file, the `CASPER_NODE_CONFIG_PATH` environment
+    /// variable, then `maybe_node_config_path` (the CLI argument).
     ///
-    /// If `maybe_node_config_path` is `None`:
-    ///   * if the casper-node-launcher's config file can be read and parsed:
-    ///     * checks the node's config file exists at the given path (returns error if not)
-    ///     * returns the read-in config
-    ///   * otherwise follows the steps for `maybe_node_config_path` is `Some` above, using the
-    ///     default value for the node config path
+    /// Equivalent to `new_with_provenance` but discards which layer won.
     pub fn new(maybe_node_config_path: Option<&str>) -> Result<Self> {
-        if let Some(node_config_path) = maybe_node_config_path {
-            if !Path::new(node_config_path).is_file() {
-                warn!(path=%node_config_path, "node config missing");
-                bail!("node config file doesn't exist at {}", node_config_path);
+        Self::new_with_provenance(maybe_node_config_path).map(|(config, _source)| config)
+    }
+
+    /// As `new`, but also returns which layer supplied `node_config_path`, so a `--print-config`
+    /// diagnostic can report it.
+    ///
+    /// The CLI argument and the `CASPER_NODE_CONFIG_PATH` env var are checked first, before ever
+    /// touching the on-disk launcher config: either one overrides whatever the file would have
+    /// supplied anyway, so a higher-precedence override lets an operator start up even if the base
+    /// launcher config (or one of its `casper.d` fragments) is corrupt. Only when neither override
+    /// is present is the on-disk file read, and only then does a parse failure there return an
+    /// error directly rather than silently falling through to the remaining layers. Whenever the
+    /// winning value didn't simply come from an already-valid on-disk file, the result is persisted
+    /// via `write`, matching the previous behaviour of bootstrapping a config on first run.
+    pub fn new_with_provenance(maybe_node_config_path: Option<&str>) -> Result<(Self, ConfigSource)> {
+        let (node_config_path, source) = if let Some(cli_path) = maybe_node_config_path {
+            (PathBuf::from(cli_path), ConfigSource::Cli)
+        } else if let Ok(env_path) = env::var(NODE_CONFIG_PATH_ENV_VAR) {
+            (PathBuf::from(env_path), ConfigSource::Env)
+        } else {
+            match Self::read() {
+                Ok(file_config) => (file_config.node_config_path, ConfigSource::File),
+                Err(error) => {
+                    let path = Self::locate()?;
+                    if path.is_file() {
+                        warn!(%error, path=%path.display(), "failed to read as config");
+                        return Err(error);
+                    }
+                    (Self::default_node_config_path(), ConfigSource::Default)
+                }
             }
+        };
+
+        if !node_config_path.is_file() {
+            warn!(path=%node_config_path.display(), "node config missing");
+            bail!(
+                "node config file doesn't exist at {}",
+                node_config_path.display()
+            );
+        }
 
-            let config = Config {
-                node_config_path: PathBuf::from(node_config_path),
-            };
+        let config = Config {
+            node_config_path,
+            cli_override: maybe_node_config_path.map(String::from),
+        };
+        if source != ConfigSource::File {
             config.write()?;
-
-            return Ok(config);
         }
 
-        match Config::read() {
-            Ok(config) => {
-                if !Path::new(&config.node_config_path).is_file() {
-                    warn!(path=%config.node_config_path.display(), "node config doesn't exist");
-                    bail!(
-                        "stored value invalid: node config file doesn't exist at {}",
-                        config.node_config_path.display()
-                    );
-                }
+        Ok((config, source))
+    }
 
-                return Ok(config);
+    /// Re-reads the on-disk config (and any `casper.d` fragments), re-applying the
+    /// `CASPER_NODE_CONFIG_PATH` env var and the original `--node-config` CLI override (if any) on
+    /// top, then swaps `self` in place — but only if the new config's `node_config_path` parses
+    /// and validates successfully.
+    ///
+    /// On failure, logs a warning and leaves `self` unchanged, returning `Ok(false)`, so a bad
+    /// on-disk edit can't crash a running node. Callers driving this from a file watcher or
+    /// `SIGHUP` handler should debounce rapid successive calls themselves, since each one re-reads
+    /// from disk.
+    pub fn reload(&mut self) -> Result<bool> {
+        match Self::new_with_provenance(self.cli_override.as_deref()) {
+            Ok((new_config, source)) => {
+                info!(path=%new_config.node_config_path.display(), ?source, "reloaded config");
+                *self = new_config;
+                Ok(true)
             }
             Err(error) => {
-                if Self::self_path().is_file() {
-                    warn!(%error, path=%Self::self_path().display(), "failed to read as config");
-                    return Err(error);
-                }
+                warn!(%error, "failed to reload config, keeping previous");
+                Ok(false)
             }
         }
-
-        Self::new(Some(
-            &Self::default_node_config_path().display().to_string(),
-        ))
     }
 
     /// Provides the actual path of the config file for the current version of casper-node.
@@ -183,25 +260,137 @@ impl Config {
         }
     }
 
-    /// Provides the path of the config file for the node-launcher.
+    /// Candidate config directories, in priority order: the `CASPER_CONFIG_DIR` override (if
+    /// set), an XDG-style user config dir, then the system default.
+    ///
+    /// This lets unprivileged users run the launcher without root-owned `/etc/casper`: the first
+    /// candidate that actually contains a launcher config wins (see `locate`), while `write` falls
+    /// back to the highest-priority candidate if none do.
+    #[cfg(not(test))]
+    fn candidate_config_dirs() -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        if let Ok(dir) = env::var(DEFAULT_CONFIG_DIR_OVERRIDE) {
+            candidates.push(PathBuf::from(dir));
+        }
+        if let Some(xdg_dir) = Self::xdg_config_dir() {
+            candidates.push(xdg_dir);
+        }
+        candidates.push(PathBuf::from(DEFAULT_CONFIG_DIR));
+        candidates
+    }
+
+    /// In tests there's a single, per-thread temp dir standing in for the config dir, so the
+    /// candidate list collapses to just that.
+    #[cfg(test)]
+    fn candidate_config_dirs() -> Vec<PathBuf> {
+        vec![Self::default_config_dir()]
+    }
+
+    /// Provides the XDG-style user config dir (`$XDG_CONFIG_HOME/casper`, falling back to
+    /// `~/.config/casper`), if either `XDG_CONFIG_HOME` or `HOME` is set.
+    #[cfg(not(test))]
+    fn xdg_config_dir() -> Option<PathBuf> {
+        let base = env::var("XDG_CONFIG_HOME").map(PathBuf::from).ok().or_else(|| {
+            env::var("HOME")
+                .ok()
+                .map(|home| PathBuf::from(home).join(".config"))
+        })?;
+        Some(base.join("casper"))
+    }
+
+    /// Provides the path of the config file for the node-launcher, for tests where
+    /// `candidate_config_dirs` always collapses to a single candidate so ambiguity can't arise.
+    #[cfg(test)]
     fn self_path() -> PathBuf {
-        Self::default_config_dir().join(CONFIG_NAME)
+        Self::locate().expect("candidate_config_dirs is single-valued under #[cfg(test)]")
+    }
+
+    /// Locates the single authoritative launcher config file among `candidate_config_dirs`.
+    ///
+    /// Returns the file in the first candidate that actually exists. Errors if the config file
+    /// exists in more than one candidate location at the same time (e.g. a stale copy left behind
+    /// in `/etc/casper` after migrating to an XDG user dir) rather than silently preferring one by
+    /// priority, since that's the classic footgun where edits land in one file while the running
+    /// process reads another. If none exist, returns the path within the highest-priority
+    /// candidate, so a subsequent `write` creates it there.
+    pub fn locate() -> Result<PathBuf> {
+        let candidates = Self::candidate_config_dirs();
+        let existing: Vec<PathBuf> = candidates
+            .iter()
+            .map(|dir| dir.join(CONFIG_NAME))
+            .filter(|path| path.is_file())
+            .collect();
+
+        if existing.len() > 1 {
+            let joined = existing
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!(
+                "{} exists in more than one location ({}); consolidate to a single file",
+                CONFIG_NAME,
+                joined
+            );
+        }
+
+        Ok(existing
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| candidates[0].join(CONFIG_NAME)))
     }
 
-    /// Constructs a new `Config` by reading it in from the hard-coded location.
+    /// Constructs a new `Config` by reading it in from the location `locate` resolves, layering any
+    /// `casper.d/*.toml` drop-in fragments found alongside it on top.
     fn read() -> Result<Self> {
-        let path = Self::self_path();
+        let path = Self::locate()?;
         debug!(path=%path.display(), "trying to read config");
         let contents = fs::read_to_string(&path)
             .with_context(|| format!("failed to read {}", path.display()))?;
-        let config = toml::from_str(&contents)?;
+        let mut value: toml::Value = toml::from_str(&contents)?;
+
+        if let Some(config_dir) = path.parent() {
+            for fragment_path in Self::config_fragments(config_dir)? {
+                debug!(path=%fragment_path.display(), "merging config fragment");
+                let fragment_contents = fs::read_to_string(&fragment_path)
+                    .with_context(|| format!("failed to read {}", fragment_path.display()))?;
+                let fragment_value: toml::Value = toml::from_str(&fragment_contents)
+                    .with_context(|| format!("failed to parse {}", fragment_path.display()))?;
+                merge_toml(&mut value, fragment_value);
+            }
+        }
+
+        let config: Config = value.try_into()?;
         info!(path=%path.display(), "read config");
         Ok(config)
     }
 
+    /// Lists the `casper.d/*.toml` fragment files alongside `config_dir`'s main config, sorted by
+    /// file name so later files win deterministically when merged.  Non-`.toml` files are
+    /// ignored, and a missing or empty `casper.d` is not an error.
+    fn config_fragments(config_dir: &Path) -> Result<Vec<PathBuf>> {
+        let fragments_dir = config_dir.join(CONFIG_FRAGMENTS_DIR_NAME);
+        if !fragments_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut fragments: Vec<PathBuf> = fs::read_dir(&fragments_dir)
+            .with_context(|| format!("failed to read dir {}", fragments_dir.display()))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect();
+        fragments.sort();
+        Ok(fragments)
+    }
+
     /// Writes `self` to the hard-coded location as a TOML-encoded file.
     fn write(&self) -> Result<()> {
-        let path = Self::self_path();
+        let path = Self::locate()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
         debug!(path=%path.display(), "trying to write config");
         let contents = toml::to_string_pretty(self)?;
         fs::write(&path, contents.as_bytes())
@@ -209,6 +398,31 @@ impl Config {
         info!(path=%path.display(), "wrote config");
         Ok(())
     }
+
+    /// Explicitly bootstraps a fresh launcher config at the default node config path, writing it
+    /// via `write` to the path `locate` resolves (an existing launcher config in any candidate
+    /// directory, otherwise the highest-priority candidate) and creating parent directories as
+    /// needed.
+    ///
+    /// Unlike `new`, this doesn't first try to read an existing config — it's meant for a
+    /// deliberate first-run/init invocation rather than requiring a pre-provisioned `/etc/casper`.
+    pub fn init() -> Result<Self> {
+        let node_config_path = Self::default_node_config_path();
+        if !node_config_path.is_file() {
+            warn!(path=%node_config_path.display(), "node config missing");
+            bail!(
+                "node config file doesn't exist at {}",
+                node_config_path.display()
+            );
+        }
+
+        let config = Config {
+            node_config_path,
+            cli_override: None,
+        };
+        config.write()?;
+        Ok(config)
+    }
 }
 
 #[cfg(test)]
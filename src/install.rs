@@ -0,0 +1,447 @@
+//! Support for fetching and staging `casper-node` releases from a remote repository.
+//!
+//! This lets the launcher provision a new version itself rather than requiring an external
+//! script or package manager to place binaries under the versioned subdir layout that
+//! `utils::versions_from_path` already understands.
+
+use std::{
+    collections::BTreeSet,
+    env,
+    fs::{self, File},
+    io::{self, Read, Write},
+    os::unix::fs::PermissionsExt,
+    path::Path,
+    str::FromStr,
+};
+
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use semver::Version;
+use sha2::{Digest, Sha256};
+use tar::Archive;
+use tracing::{debug, info};
+
+use crate::utils::{self, VersionSpec};
+
+/// Environment variable used to override the default release repository base URL.
+const DIST_URL_OVERRIDE: &str = "CASPER_DIST_URL";
+/// The default base URL releases are fetched from.
+const DEFAULT_DIST_URL: &str = "https://binaries.casperlabs.io/releases";
+/// Extension of the checksum sidecar file published alongside each release archive.
+const CHECKSUM_EXTENSION: &str = "sha256";
+/// The name of the casper-node binary within an unpacked release archive.
+const NODE_BINARY_NAME: &str = "casper-node";
+/// The name of the casper-node config file within an unpacked release archive.
+const NODE_CONFIG_NAME: &str = "config.toml";
+
+/// Returns the base URL to fetch node releases from, honouring `CASPER_DIST_URL` if set.
+pub(crate) fn dist_url() -> String {
+    env::var(DIST_URL_OVERRIDE).unwrap_or_else(|_| DEFAULT_DIST_URL.to_string())
+}
+
+/// Builds the URL of the release archive for `version` on this host's OS/arch.
+fn release_url(base_url: &str, version: &Version) -> String {
+    format!(
+        "{}/{}/casper-node-{}-{}.tar.gz",
+        base_url.trim_end_matches('/'),
+        version,
+        env::consts::OS,
+        env::consts::ARCH,
+    )
+}
+
+/// Builds the URL of the manifest listing every version published at `base_url`.
+fn manifest_url(base_url: &str) -> String {
+    format!("{}/manifest.json", base_url.trim_end_matches('/'))
+}
+
+/// Fetches and parses the list of versions published at `<base_url>/manifest.json` (a JSON array
+/// of version strings), letting an operator request "install latest" or a semver requirement
+/// without knowing the exact version number.
+fn fetch_manifest(base_url: &str) -> Result<BTreeSet<Version>> {
+    let url = manifest_url(base_url);
+    let body = ureq::get(&url)
+        .call()
+        .with_context(|| format!("failed to request {}", url))?
+        .into_string()
+        .with_context(|| format!("failed to read body of {}", url))?;
+    parse_manifest_body(&url, &body)
+}
+
+/// Parses `body` as a JSON array of version strings, as published at `<base_url>/manifest.json`.
+/// Split out from `fetch_manifest` so the parsing logic can be exercised without a network call.
+fn parse_manifest_body(url: &str, body: &str) -> Result<BTreeSet<Version>> {
+    let raw_versions: Vec<String> = serde_json::from_str(body)
+        .with_context(|| format!("failed to parse {} as a JSON array of versions", url))?;
+    raw_versions
+        .iter()
+        .map(|raw| {
+            Version::from_str(raw)
+                .with_context(|| format!("{} lists invalid version '{}'", url, raw))
+        })
+        .collect()
+}
+
+/// Resolves `spec` to a concrete `Version`, consulting the remote manifest unless `spec` is
+/// already an exact version.
+fn resolve_remote_version(base_url: &str, spec: &VersionSpec) -> Result<Version> {
+    match spec {
+        VersionSpec::Exact(version) => Ok(version.clone()),
+        _ => {
+            let manifest = fetch_manifest(base_url)?;
+            if manifest.is_empty() {
+                bail!("{} published an empty manifest", manifest_url(base_url));
+            }
+            utils::resolve_version_spec_from_installed(manifest, spec)
+        }
+    }
+}
+
+/// Downloads `url` to `dest`, logging progress as a running byte count.
+fn download_with_progress(url: &str, dest: &Path) -> Result<()> {
+    let response = ureq::get(url)
+        .call()
+        .with_context(|| format!("failed to request {}", url))?;
+    let total_len = response
+        .header("Content-Length")
+        .and_then(|len| len.parse::<u64>().ok());
+
+    let mut reader = response.into_reader();
+    let mut file =
+        File::create(dest).with_context(|| format!("failed to create {}", dest.display()))?;
+
+    let mut buffer = [0u8; 64 * 1024];
+    let mut downloaded = 0u64;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        file.write_all(&buffer[..read])?;
+        downloaded += read as u64;
+        match total_len {
+            Some(total) => debug!(url, downloaded, total, "downloading"),
+            None => debug!(url, downloaded, "downloading"),
+        }
+    }
+    info!(url, bytes = downloaded, "finished download");
+    Ok(())
+}
+
+/// Fetches the published SHA-256 checksum for the release at `archive_url` from its sibling
+/// `<archive_url>.sha256` file.
+fn fetch_checksum(archive_url: &str) -> Result<String> {
+    let checksum_url = format!("{}.{}", archive_url, CHECKSUM_EXTENSION);
+    let body = ureq::get(&checksum_url)
+        .call()
+        .with_context(|| format!("failed to request {}", checksum_url))?
+        .into_string()
+        .with_context(|| format!("failed to read body of {}", checksum_url))?;
+    let checksum = body
+        .split_whitespace()
+        .next()
+        .with_context(|| format!("{} returned an empty checksum", checksum_url))?;
+    Ok(checksum.to_lowercase())
+}
+
+/// Verifies that the SHA-256 digest of the file at `path` matches `expected_checksum`.
+fn verify_checksum(path: &Path, expected_checksum: &str) -> Result<()> {
+    let mut file =
+        File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    let actual_checksum = hex::encode(hasher.finalize());
+    if actual_checksum != expected_checksum {
+        bail!(
+            "checksum mismatch for {}: expected {}, got {}",
+            path.display(),
+            expected_checksum,
+            actual_checksum
+        );
+    }
+    Ok(())
+}
+
+/// Extracts the `.tar.gz` archive at `archive_path` into `dest_dir`.
+fn unpack_archive(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+    let file = File::open(archive_path)
+        .with_context(|| format!("failed to open {}", archive_path.display()))?;
+    let mut archive = Archive::new(GzDecoder::new(file));
+    archive.unpack(dest_dir).with_context(|| {
+        format!(
+            "failed to unpack {} into {}",
+            archive_path.display(),
+            dest_dir.display()
+        )
+    })
+}
+
+/// Resolves `spec` against the remote manifest (unless it's already an exact version) and
+/// installs the result, returning the concrete `Version` that was staged.
+///
+/// This is what lets an operator "install latest" or a semver requirement without first listing
+/// releases by hand.
+pub(crate) fn install_version_spec(
+    base_url: &str,
+    binary_root_dir: &Path,
+    config_root_dir: &Path,
+    spec: &VersionSpec,
+    force: bool,
+) -> Result<Version> {
+    let version = resolve_remote_version(base_url, spec)?;
+    install_version(base_url, binary_root_dir, config_root_dir, &version, force)?;
+    Ok(version)
+}
+
+/// Installs `version` of `casper-node` into `binary_root_dir`/`config_root_dir`, fetching the
+/// release archive from `base_url`.
+///
+/// The binary and config are unpacked into `<root>/<major>_<minor>_<patch>/`, matching the
+/// directory naming `utils::versions_from_path` already understands. Refuses to overwrite an
+/// existing install unless `force` is `true`.
+pub(crate) fn install_version(
+    base_url: &str,
+    binary_root_dir: &Path,
+    config_root_dir: &Path,
+    version: &Version,
+    force: bool,
+) -> Result<()> {
+    let subdir_name = version.to_string().replace('.', "_");
+    let binary_install_dir = binary_root_dir.join(&subdir_name);
+    let config_install_dir = config_root_dir.join(&subdir_name);
+
+    if !force && (binary_install_dir.exists() || config_install_dir.exists()) {
+        bail!(
+            "{} is already installed (use --force to overwrite)",
+            version
+        );
+    }
+
+    let archive_url = release_url(base_url, version);
+    let temp_dir = tempfile::tempdir().context("failed to create temp dir for download")?;
+    let archive_path = temp_dir.path().join(format!("{}.tar.gz", subdir_name));
+
+    info!(url = %archive_url, %version, "downloading casper-node release");
+    download_with_progress(&archive_url, &archive_path)?;
+
+    let checksum = fetch_checksum(&archive_url)?;
+    verify_checksum(&archive_path, &checksum)?;
+
+    let unpack_dir = temp_dir.path().join("unpacked");
+    fs::create_dir_all(&unpack_dir)
+        .with_context(|| format!("failed to create {}", unpack_dir.display()))?;
+    unpack_archive(&archive_path, &unpack_dir)?;
+
+    let unpacked_binary = unpack_dir.join(NODE_BINARY_NAME);
+    let unpacked_config = unpack_dir.join(NODE_CONFIG_NAME);
+    if !unpacked_binary.is_file() || !unpacked_config.is_file() {
+        bail!(
+            "release archive for {} didn't contain both {} and {}",
+            version,
+            NODE_BINARY_NAME,
+            NODE_CONFIG_NAME
+        );
+    }
+
+    if binary_install_dir.exists() {
+        fs::remove_dir_all(&binary_install_dir)
+            .with_context(|| format!("failed to remove {}", binary_install_dir.display()))?;
+    }
+    if config_install_dir.exists() {
+        fs::remove_dir_all(&config_install_dir)
+            .with_context(|| format!("failed to remove {}", config_install_dir.display()))?;
+    }
+    fs::create_dir_all(&binary_install_dir)
+        .with_context(|| format!("failed to create {}", binary_install_dir.display()))?;
+    fs::create_dir_all(&config_install_dir)
+        .with_context(|| format!("failed to create {}", config_install_dir.display()))?;
+
+    let installed_binary_path = binary_install_dir.join(NODE_BINARY_NAME);
+    fs::rename(&unpacked_binary, &installed_binary_path).with_context(|| {
+        format!(
+            "failed to move {} into {}",
+            unpacked_binary.display(),
+            installed_binary_path.display()
+        )
+    })?;
+    let mut permissions = fs::metadata(&installed_binary_path)?.permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(&installed_binary_path, permissions)?;
+
+    let installed_config_path = config_install_dir.join(NODE_CONFIG_NAME);
+    fs::rename(&unpacked_config, &installed_config_path).with_context(|| {
+        format!(
+            "failed to move {} into {}",
+            unpacked_config.display(),
+            installed_config_path.display()
+        )
+    })?;
+
+    info!(%version, path = %binary_install_dir.display(), "installed casper-node");
+    Ok(())
+}
+
+/// Removes the installed binary and config subdirs for `version`.
+pub(crate) fn uninstall_version(
+    binary_root_dir: &Path,
+    config_root_dir: &Path,
+    version: &Version,
+) -> Result<()> {
+    let subdir_name = version.to_string().replace('.', "_");
+    let binary_dir = binary_root_dir.join(&subdir_name);
+    let config_dir = config_root_dir.join(&subdir_name);
+
+    if !binary_dir.exists() && !config_dir.exists() {
+        bail!("{} is not installed", version);
+    }
+
+    if binary_dir.exists() {
+        fs::remove_dir_all(&binary_dir)
+            .with_context(|| format!("failed to remove {}", binary_dir.display()))?;
+    }
+    if config_dir.exists() {
+        fs::remove_dir_all(&config_dir)
+            .with_context(|| format!("failed to remove {}", config_dir.display()))?;
+    }
+
+    info!(%version, "uninstalled casper-node");
+    Ok(())
+}
+
+/// Computes the set of installed versions a `prune --keep N` run would remove: every version
+/// under `binary_root_dir` except the `keep` highest, always excluding `retained_version` (e.g.
+/// the currently forced/running version) even if it would otherwise be pruned.
+pub(crate) fn versions_to_prune(
+    binary_root_dir: &Path,
+    keep: usize,
+    retained_version: Option<&Version>,
+) -> Result<Vec<Version>> {
+    let mut ascending: Vec<Version> = utils::versions_from_path(binary_root_dir)?
+        .into_iter()
+        .collect();
+    let cutoff = ascending.len().saturating_sub(keep);
+    let mut to_prune: Vec<Version> = ascending.drain(..cutoff).collect();
+    if let Some(retained) = retained_version {
+        to_prune.retain(|version| version != retained);
+    }
+    Ok(to_prune)
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::{write::GzEncoder, Compression};
+
+    use super::*;
+
+    /// Builds a `.tar.gz` archive at `archive_path` containing a single entry at `entry_path`
+    /// with the given `contents`.
+    fn write_archive_with_entry(archive_path: &Path, entry_path: &str, contents: &[u8]) {
+        let file = File::create(archive_path).unwrap();
+        let encoder = GzEncoder::new(file, Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, entry_path, contents)
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    /// As `write_archive_with_entry`, but writes `entry_path` straight into the header's raw name
+    /// bytes rather than going through `Header::set_path`/`Builder::append_data`, both of which
+    /// reject `..` path components outright. This is what lets the test construct a malicious
+    /// archive at all: a real attacker isn't bound by this crate's own safety checks either.
+    fn write_archive_with_path_traversal_entry(archive_path: &Path, entry_path: &str, contents: &[u8]) {
+        let file = File::create(archive_path).unwrap();
+        let encoder = GzEncoder::new(file, Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+
+        let mut header = tar::Header::new_gnu();
+        header.as_old_mut().name[..entry_path.len()].copy_from_slice(entry_path.as_bytes());
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, contents).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn should_not_extract_path_traversal_entry_outside_dest_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive_path = temp_dir.path().join("malicious.tar.gz");
+        write_archive_with_path_traversal_entry(&archive_path, "../../etc/passwd", b"pwned");
+
+        let dest_dir = temp_dir.path().join("unpacked");
+        fs::create_dir_all(&dest_dir).unwrap();
+
+        // `tar`'s own unpacker silently skips entries whose path contains `..` rather than
+        // erroring, so the entry is neither extracted into `dest_dir` nor anywhere above it.
+        unpack_archive(&archive_path, &dest_dir).unwrap();
+        assert!(!dest_dir.join("etc").exists());
+        // `../../` from `<temp_dir>/unpacked` resolves to `temp_dir`'s parent.
+        assert!(!temp_dir.path().parent().unwrap().join("etc").join("passwd").exists());
+    }
+
+    #[test]
+    fn should_unpack_well_formed_archive() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let archive_path = temp_dir.path().join("good.tar.gz");
+        write_archive_with_entry(&archive_path, NODE_BINARY_NAME, b"a fine binary");
+
+        let dest_dir = temp_dir.path().join("unpacked");
+        fs::create_dir_all(&dest_dir).unwrap();
+
+        unpack_archive(&archive_path, &dest_dir).unwrap();
+        assert_eq!(
+            fs::read(dest_dir.join(NODE_BINARY_NAME)).unwrap(),
+            b"a fine binary"
+        );
+    }
+
+    #[test]
+    fn should_reject_checksum_mismatch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("casper-node");
+        fs::write(&path, b"some binary contents").unwrap();
+
+        let error = verify_checksum(&path, "0000000000000000000000000000000000000000000000000000000000000000")
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with(&format!("checksum mismatch for {}", path.display())));
+    }
+
+    #[test]
+    fn should_verify_matching_checksum() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("casper-node");
+        fs::write(&path, b"some binary contents").unwrap();
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"some binary contents");
+        let checksum = hex::encode(hasher.finalize());
+
+        verify_checksum(&path, &checksum).unwrap();
+    }
+
+    #[test]
+    fn should_fail_to_resolve_remote_version_on_malformed_manifest() {
+        let error = parse_manifest_body("http://example.invalid/manifest.json", "not json")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("failed to parse http://example.invalid/manifest.json as a JSON array of versions"));
+    }
+
+    #[test]
+    fn should_fail_to_resolve_remote_version_on_invalid_version_in_manifest() {
+        let error = parse_manifest_body(
+            "http://example.invalid/manifest.json",
+            r#"["1.0.0", "not-a-version"]"#,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("lists invalid version 'not-a-version'"));
+    }
+}
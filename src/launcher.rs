@@ -1,15 +1,18 @@
-#[cfg(test)]
-use std::thread;
 #[cfg(not(test))]
-use std::{env, process};
+use std::{env, process, str::FromStr};
 use std::{
+    cell::RefCell,
+    collections::BTreeSet,
     fmt::Debug,
-    fs, mem,
+    fs, io, mem,
     path::{Path, PathBuf},
     process::Command,
+    thread,
+    time::{Duration, Instant},
 };
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
 #[cfg(test)]
 use once_cell::sync::Lazy;
 use semver::Version;
@@ -19,9 +22,20 @@ use tempfile::TempDir;
 use tracing::{debug, error, info, warn};
 
 use crate::utils::{self, NodeExitCode};
+use crate::verify;
 
 /// The name of the file for the on-disk record of the node-launcher's current state.
 const STATE_FILE_NAME: &str = "casper-node-launcher-state.toml";
+/// The name of the file for the on-disk, append-only record of every `step()` the launcher has
+/// taken. Kept separate from `STATE_FILE_NAME`, much like cargo's `.crates.toml`/`.crates2.json`
+/// split: the state file is the single source of truth the launcher resumes from, while the
+/// history file is purely an audit trail for operators and is never read back to decide behavior.
+const HISTORY_FILE_NAME: &str = "casper-node-launcher-history.json";
+/// The name of the file for the on-disk record of per-version consecutive-crash counts backing the
+/// auto-block mechanism (see `Launcher::blocked_versions`). Kept separate from both the state and
+/// history files: unlike the history file it's consulted to decide behaviour, but unlike the state
+/// file it's fine to lose, since losing it just gives a known-bad version a fresh count.
+const BLOCKLIST_FILE_NAME: &str = "casper-node-launcher-blocklist.json";
 /// The path of the node-launcher shutdown script.
 #[cfg(not(test))]
 const SHUTDOWN_SCRIPT_PATH: &str = "/etc/casper/casper_shutdown_script";
@@ -47,6 +61,23 @@ const NODE_CONFIG_NAME: &str = "config.toml";
 #[cfg(not(test))]
 const CONFIG_ROOT_DIR_OVERRIDE: &str = "CASPER_CONFIG_DIR";
 
+/// Environment variable overriding the number of old installed versions retained on disk after an
+/// upgrade. `0` (the default) means unlimited, i.e. nothing is pruned.
+#[cfg(not(test))]
+const RETENTION_LIMIT_OVERRIDE: &str = "CASPER_RETENTION_LIMIT";
+
+/// Environment variable giving a comma-separated list of exact versions that must never be
+/// selected as an upgrade or downgrade target, regardless of what's installed.
+#[cfg(not(test))]
+const BLOCKED_VERSIONS_OVERRIDE: &str = "CASPER_BLOCKED_VERSIONS";
+/// Environment variable overriding the number of consecutive unexpected exits before a version is
+/// auto-blocked. `0` disables auto-blocking.
+#[cfg(not(test))]
+const AUTO_BLOCK_THRESHOLD_OVERRIDE: &str = "CASPER_AUTO_BLOCK_THRESHOLD";
+/// The production default for `AUTO_BLOCK_THRESHOLD_OVERRIDE`.
+#[cfg(not(test))]
+const DEFAULT_AUTO_BLOCK_THRESHOLD: u32 = 3;
+
 /// The subcommands and args for casper-node.
 const MIGRATE_SUBCOMMAND: &str = "migrate-data";
 const OLD_CONFIG_ARG: &str = "--old-config";
@@ -92,6 +123,92 @@ impl Default for State {
     }
 }
 
+/// Reads the persisted launcher state (if any) under `config_root_dir` and returns the version it
+/// currently references, ignoring the outgoing `old_info` of an in-progress data migration.
+///
+/// Returns `None` if no state has been persisted yet or it can't be parsed; callers that need
+/// this purely as a "never delete this version" safeguard should treat that as "nothing to
+/// protect" rather than an error.
+pub(crate) fn current_version(config_root_dir: &Path) -> Option<Version> {
+    let state_path = config_root_dir.join(STATE_FILE_NAME);
+    let contents = fs::read_to_string(state_path).ok()?;
+    let state: State = toml::from_str(&contents).ok()?;
+    Some(match state {
+        State::RunNodeAsValidator(info) => info.version,
+        State::MigrateData { new_info, .. } => new_info.version,
+    })
+}
+
+/// The kind of thing a recorded `step()` did, classifying the whole step rather than just which
+/// subcommand was invoked: an unexceptional validator or data-migration run is recorded as such,
+/// but one whose `NodeExitCode` triggered a downgrade or the shutdown script is recorded as
+/// `Downgrader`/`ShutdownScript` instead, since that's the more useful fact for an operator
+/// auditing the timeline.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum RunMode {
+    /// Ran the node in validator mode.
+    Validator,
+    /// Ran the node in data-migration mode.
+    DataMigration,
+    /// Ran the node, which exited requesting a downgrade.
+    Downgrader,
+    /// Ran the node, which exited requesting the shutdown script.
+    ShutdownScript,
+}
+
+/// A single recorded `step()`, as persisted in the history file.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct HistoryEntry {
+    /// The version of the node binary that was run.
+    pub version: Version,
+    /// What kind of run this was.
+    pub mode: RunMode,
+    /// When the node process was spawned.
+    pub started_at: DateTime<Utc>,
+    /// When the node process exited.
+    pub finished_at: DateTime<Utc>,
+    /// The exit code the node process (or, for `Downgrader`/`ShutdownScript`, the node process
+    /// that triggered the transition) returned.
+    pub exit_code: NodeExitCode,
+    /// The shutdown script's own exit code, which the launcher process then exits with (surfaced
+    /// by `Launcher::exit_code` in test builds). Only ever set when `mode` is `ShutdownScript`.
+    pub shutdown_script_exit_code: Option<i32>,
+}
+
+/// Tracks how many times in a row a given version has exited with an unrecognized code, backing
+/// the auto-block half of `Launcher::blocked_versions`. Persisted to disk so a known-bad release
+/// doesn't get a fresh three strikes every time the launcher restarts.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+struct CrashCount {
+    version: Version,
+    consecutive_crashes: u32,
+}
+
+/// Governs how `Launcher::run` responds to the current version's node process exiting with a
+/// code other than the recognized `NodeExitCode` values.
+///
+/// On such an exit, the launcher retries the same version after an exponentially increasing
+/// delay, up to `max_retries` times.  If more than `max_retries` crashes occur within
+/// `crash_window` of each other, that's treated as a crash-loop and `run` returns the error
+/// instead of retrying again.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RestartPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub crash_window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        RestartPolicy {
+            max_retries: 5,
+            base_delay: Duration::from_secs(1),
+            crash_window: Duration::from_secs(60),
+        }
+    }
+}
+
 /// The object responsible for running the casper-node as a child process.
 ///
 /// It operates as a state machine, iterating between running the node in validator mode and running
@@ -104,6 +221,23 @@ pub struct Launcher {
     binary_root_dir: PathBuf,
     config_root_dir: PathBuf,
     state: State,
+    restart_policy: RestartPolicy,
+    retention_limit: usize,
+    /// Cache for `installed_versions`, populated lazily so `status` can call it without a second
+    /// directory scan.
+    installed_versions_cache: RefCell<Option<Vec<Version>>>,
+    /// The upgrade/run history loaded from disk at construction and appended to on every `step()`.
+    history: Vec<HistoryEntry>,
+    /// The config-supplied version blocklist (from `CASPER_BLOCKED_VERSIONS`), combined with
+    /// `crash_counts` by `blocked_versions` to decide what's excluded from upgrade/downgrade
+    /// selection.
+    configured_blocked_versions: BTreeSet<Version>,
+    /// Per-version consecutive-crash counts backing the auto-block mechanism, loaded from disk at
+    /// construction and updated by `record_crash`/`record_clean_validator_run`.
+    crash_counts: Vec<CrashCount>,
+    /// The number of consecutive unexpected exits before a version is auto-blocked (`0` disables
+    /// auto-blocking).
+    auto_block_threshold: u32,
     #[cfg(test)]
     exit_code: Option<i32>,
 }
@@ -114,12 +248,58 @@ impl Default for Launcher {
             binary_root_dir: Self::binary_root_dir(),
             config_root_dir: Self::config_root_dir(),
             state: Default::default(),
+            restart_policy: Default::default(),
+            retention_limit: Self::default_retention_limit(),
+            installed_versions_cache: RefCell::new(None),
+            history: Vec::new(),
+            configured_blocked_versions: Self::default_configured_blocked_versions(),
+            crash_counts: Vec::new(),
+            auto_block_threshold: Self::default_auto_block_threshold(),
             #[cfg(test)]
             exit_code: None,
         }
     }
 }
 
+/// A snapshot of the launcher's current state and the versions available to it, for tooling and
+/// operators to query without parsing `casper-node-launcher-state.toml` by hand.
+#[derive(Serialize, Debug)]
+pub struct Status {
+    /// The version the launcher is currently running (or migrating to).
+    pub active_version: Version,
+    /// The path to the active version's node binary.
+    pub binary_path: PathBuf,
+    /// The path to the active version's node config.
+    pub config_path: PathBuf,
+    /// Whether the launcher is currently mid-upgrade, running a data migration.
+    pub migrating: bool,
+    /// Every version installed under `binary_root_dir`, ascending.
+    pub installed_versions: Vec<Version>,
+}
+
+/// A single problem found by `Launcher::validate_upgrade_path`, anchored to the version it
+/// affects.
+#[derive(Debug)]
+pub struct UpgradeDefect {
+    pub version: Version,
+    pub problem: String,
+}
+
+/// The outcome of a non-executing walk of the upgrade chain. See
+/// `Launcher::validate_upgrade_path`.
+#[derive(Debug, Default)]
+pub struct UpgradeValidationReport {
+    pub defects: Vec<UpgradeDefect>,
+}
+
+impl UpgradeValidationReport {
+    /// Returns `true` if the walk found no problems, i.e. it's safe to let the launcher act on
+    /// this upgrade chain.
+    pub fn is_sound(&self) -> bool {
+        self.defects.is_empty()
+    }
+}
+
 impl Launcher {
     /// Constructs a new `Launcher`.
     ///
@@ -127,9 +307,25 @@ impl Launcher {
     /// it will search for the latest installed version of casper-node and start running it in
     /// validator mode.
     ///
-    /// The launcher may also be instructed to run a fixed version of the node. In such case
-    /// it'll run it in validator mode and store the version in the local state.
-    pub fn new(forced_version: Option<Version>) -> Result<Self> {
+    /// The launcher may also be instructed to run a fixed version of the node, given as a
+    /// `utils::VersionSpec` (an exact version, a semver requirement, or the `latest`/`previous`/
+    /// `lowest` aliases) rather than requiring the caller to already know the precise installed
+    /// version. In such case it'll run it in validator mode and store the resolved concrete
+    /// version in the local state.
+    ///
+    /// Either way, a freshly selected version (forced, or the most recent on a first run) is
+    /// verified via `verify::verify_binary` before being committed to state, so a corrupted or
+    /// unsigned staged binary is never selected in the first place. Resuming from a previously
+    /// persisted state skips this, since its binary was already verified when first selected, and
+    /// will be verified again regardless before `step` spawns it.
+    ///
+    /// Also loads the persisted upgrade/run history, if any, via `load_history` (unlike the state
+    /// file, a corrupt history is rotated aside and logged rather than treated as a hard error,
+    /// since it's only an audit trail, not something the launcher needs to resume correctly), and
+    /// the persisted per-version crash counts via `load_crash_counts`, which together with any
+    /// `CASPER_BLOCKED_VERSIONS` entries determine which versions `blocked_versions` excludes from
+    /// upgrade/downgrade selection.
+    pub fn new(forced_version_spec: Option<utils::VersionSpec>) -> Result<Self> {
         let installed_binary_versions = utils::versions_from_path(&Self::binary_root_dir())?;
         let installed_config_versions = utils::versions_from_path(&Self::config_root_dir())?;
 
@@ -141,26 +337,23 @@ impl Launcher {
             );
         }
 
-        match forced_version {
-            Some(forced_version) => {
-                // Run the requested node version, if available.
-                if installed_binary_versions.contains(&forced_version) {
-                    let mut launcher = Launcher::default();
-                    launcher.set_state(State::RunNodeAsValidator(
-                        launcher.new_node_info(forced_version),
-                    ))?;
-                    Ok(launcher)
-                } else {
-                    info!(%forced_version, "the requested version is not installed");
-                    bail!(
-                        "the requested version ({}) is not installed",
-                        forced_version
-                    )
-                }
+        match forced_version_spec {
+            Some(spec) => {
+                let forced_version =
+                    utils::resolve_version_spec_from_installed(installed_binary_versions, &spec)?;
+                let mut launcher = Launcher::default();
+                launcher.history = launcher.load_history();
+                launcher.crash_counts = launcher.load_crash_counts();
+                let node_info = launcher.new_node_info(forced_version);
+                verify::verify_binary(&node_info.version, &node_info.binary_path)?;
+                launcher.set_state(State::RunNodeAsValidator(node_info))?;
+                Ok(launcher)
             }
             None => {
                 // If state file is missing, run most recent node version. Otherwise, resume from state.
                 let mut launcher = Launcher::default();
+                launcher.history = launcher.load_history();
+                launcher.crash_counts = launcher.load_crash_counts();
 
                 let maybe_state = launcher.try_load_state()?;
                 match maybe_state {
@@ -171,6 +364,7 @@ impl Launcher {
                     }
                     None => {
                         let node_info = launcher.new_node_info(launcher.most_recent_version()?);
+                        verify::verify_binary(&node_info.version, &node_info.binary_path)?;
                         launcher.set_state(State::RunNodeAsValidator(node_info))?;
                         Ok(launcher)
                     }
@@ -179,10 +373,145 @@ impl Launcher {
         }
     }
 
+    /// Overrides the default restart/crash-loop policy applied by `run`.
+    pub fn set_restart_policy(&mut self, restart_policy: RestartPolicy) {
+        self.restart_policy = restart_policy;
+    }
+
+    /// Overrides the default number of old installed versions retained after an upgrade (`0` means
+    /// unlimited). See `prune_old_versions`.
+    pub fn set_retention_limit(&mut self, retention_limit: usize) {
+        self.retention_limit = retention_limit;
+    }
+
+    /// Provides the default version retention limit, read from `CASPER_RETENTION_LIMIT` if set.
+    ///
+    /// `0` (the default, and always the value used in tests unless overridden via
+    /// `set_retention_limit`) means unlimited, i.e. `prune_old_versions` is a no-op.
+    fn default_retention_limit() -> usize {
+        #[cfg(not(test))]
+        {
+            env::var(RETENTION_LIMIT_OVERRIDE)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0)
+        }
+        #[cfg(test)]
+        {
+            0
+        }
+    }
+
+    /// Overrides the config-supplied version blocklist (in addition to whatever the auto-block
+    /// mechanism has accumulated). See `blocked_versions`.
+    pub fn set_blocked_versions(&mut self, blocked_versions: BTreeSet<Version>) {
+        self.configured_blocked_versions = blocked_versions;
+    }
+
+    /// Provides the default config-supplied version blocklist, read from `CASPER_BLOCKED_VERSIONS`
+    /// if set (a comma-separated list of exact versions). Invalid entries are logged and skipped
+    /// rather than treated as a hard error.
+    fn default_configured_blocked_versions() -> BTreeSet<Version> {
+        #[cfg(not(test))]
+        {
+            env::var(BLOCKED_VERSIONS_OVERRIDE)
+                .ok()
+                .map(|value| {
+                    value
+                        .split(',')
+                        .filter_map(|raw| {
+                            let raw = raw.trim();
+                            if raw.is_empty() {
+                                return None;
+                            }
+                            match Version::from_str(raw) {
+                                Ok(version) => Some(version),
+                                Err(error) => {
+                                    warn!(
+                                        %error,
+                                        value = raw,
+                                        "ignoring invalid entry in CASPER_BLOCKED_VERSIONS"
+                                    );
+                                    None
+                                }
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        }
+        #[cfg(test)]
+        {
+            BTreeSet::new()
+        }
+    }
+
+    /// Overrides the default number of consecutive unexpected exits before a version is
+    /// auto-blocked (`0` disables auto-blocking). See `blocked_versions`.
+    pub fn set_auto_block_threshold(&mut self, auto_block_threshold: u32) {
+        self.auto_block_threshold = auto_block_threshold;
+    }
+
+    /// Provides the default auto-block threshold, read from `CASPER_AUTO_BLOCK_THRESHOLD` if set.
+    ///
+    /// `0` disables auto-blocking. The production default is 3 consecutive unexpected exits; tests
+    /// get `0` unless they opt in via `set_auto_block_threshold`, matching `default_retention_limit`.
+    fn default_auto_block_threshold() -> u32 {
+        #[cfg(not(test))]
+        {
+            env::var(AUTO_BLOCK_THRESHOLD_OVERRIDE)
+                .ok()
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(DEFAULT_AUTO_BLOCK_THRESHOLD)
+        }
+        #[cfg(test)]
+        {
+            0
+        }
+    }
+
     /// Runs the launcher, blocking indefinitely.
+    ///
+    /// If a step fails because the node exited with an unrecognized code, the same version is
+    /// retried after an exponentially increasing delay per `self.restart_policy`, unless a
+    /// crash-loop is detected (more than `max_retries` such failures within `crash_window`), in which case
+    /// the error is returned.  Any other error (e.g. no higher version to upgrade to) is returned
+    /// immediately, as before.
     pub fn run(&mut self) -> Result<()> {
+        let mut crash_timestamps: Vec<Instant> = Vec::new();
         loop {
-            self.step()?;
+            match self.step() {
+                Ok(()) => continue,
+                Err(error) => {
+                    if !error
+                        .to_string()
+                        .ends_with(utils::UNEXPECTED_EXIT_SUFFIX)
+                    {
+                        return Err(error);
+                    }
+
+                    let now = Instant::now();
+                    crash_timestamps.retain(|timestamp| {
+                        now.duration_since(*timestamp) <= self.restart_policy.crash_window
+                    });
+                    crash_timestamps.push(now);
+
+                    if crash_timestamps.len() as u32 > self.restart_policy.max_retries {
+                        warn!(
+                            crashes = crash_timestamps.len(),
+                            window_secs = self.restart_policy.crash_window.as_secs(),
+                            %error,
+                            "crash-loop detected, aborting instead of retrying further"
+                        );
+                        return Err(error);
+                    }
+
+                    let attempt = crash_timestamps.len() as u32;
+                    let delay = self.restart_policy.base_delay * 2u32.pow(attempt - 1);
+                    warn!(%error, attempt, ?delay, "node exited unexpectedly, retrying after backoff");
+                    thread::sleep(delay);
+                }
+            }
         }
     }
 
@@ -236,28 +565,240 @@ impl Launcher {
         Ok(())
     }
 
-    /// Gets the most recent installed binary version.
+    /// Provides the path of the file for recording the launcher's upgrade/run history.
+    fn history_path(&self) -> PathBuf {
+        self.config_root_dir.join(HISTORY_FILE_NAME)
+    }
+
+    /// Loads the persisted upgrade/run history from disk, if present.
+    ///
+    /// Unlike `try_load_state`, a missing or corrupt history file is never a hard error: if it's
+    /// corrupt, it's rotated aside (so `append_history` starts a fresh one) and the problem is
+    /// logged, since losing the audit trail shouldn't stop the launcher from starting.
+    fn load_history(&self) -> Vec<HistoryEntry> {
+        let path = self.history_path();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!(%error, path=%path.display(), "failed to read history file, starting a fresh history");
+                }
+                return Vec::new();
+            }
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(history) => history,
+            Err(error) => {
+                warn!(
+                    %error,
+                    path=%path.display(),
+                    "history file is corrupt, rotating it aside and starting a fresh history"
+                );
+                let rotated_path = path.with_extension("json.corrupt");
+                if let Err(error) = fs::rename(&path, &rotated_path) {
+                    warn!(%error, path=%path.display(), "failed to rotate corrupt history file aside");
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    /// Appends `entry` to the in-memory history and persists the whole history to disk as JSON.
+    ///
+    /// Persisting is best-effort: a failure to encode or write it is logged and otherwise ignored,
+    /// since the history file is only an audit trail and shouldn't turn an otherwise-successful
+    /// step into a failed one.
+    fn append_history(&mut self, entry: HistoryEntry) {
+        self.history.push(entry);
+
+        let path = self.history_path();
+        let contents = match serde_json::to_string_pretty(&self.history) {
+            Ok(contents) => contents,
+            Err(error) => {
+                warn!(%error, "failed to encode history as JSON");
+                return;
+            }
+        };
+        if let Err(error) = fs::write(&path, contents.as_bytes()) {
+            warn!(%error, path=%path.display(), "failed to write history file");
+        }
+    }
+
+    /// Returns the persisted upgrade/run history, oldest first, for an operator to audit the
+    /// upgrade timeline.
+    pub fn history(&self) -> &[HistoryEntry] {
+        &self.history
+    }
+
+    /// Provides the path of the file for recording per-version consecutive-crash counts.
+    fn blocklist_path(&self) -> PathBuf {
+        self.config_root_dir.join(BLOCKLIST_FILE_NAME)
+    }
+
+    /// Loads the persisted crash counts from disk, if present.
     ///
-    /// Returns an error when no correct versions can be detected.
+    /// As with `load_history`, a missing or corrupt file is never a hard error: it's only
+    /// bookkeeping for the auto-block mechanism, so losing it just gives a known-bad version a
+    /// fresh count rather than stopping the launcher from starting.
+    fn load_crash_counts(&self) -> Vec<CrashCount> {
+        let path = self.blocklist_path();
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) => {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!(%error, path=%path.display(), "failed to read blocklist file, starting with no crash counts");
+                }
+                return Vec::new();
+            }
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(crash_counts) => crash_counts,
+            Err(error) => {
+                warn!(
+                    %error,
+                    path=%path.display(),
+                    "blocklist file is corrupt, rotating it aside and starting with no crash counts"
+                );
+                let rotated_path = path.with_extension("json.corrupt");
+                if let Err(error) = fs::rename(&path, &rotated_path) {
+                    warn!(%error, path=%path.display(), "failed to rotate corrupt blocklist file aside");
+                }
+                Vec::new()
+            }
+        }
+    }
+
+    /// Persists `self.crash_counts` to disk as JSON. Best-effort, like `append_history`.
+    fn persist_crash_counts(&self) {
+        let path = self.blocklist_path();
+        let contents = match serde_json::to_string_pretty(&self.crash_counts) {
+            Ok(contents) => contents,
+            Err(error) => {
+                warn!(%error, "failed to encode blocklist as JSON");
+                return;
+            }
+        };
+        if let Err(error) = fs::write(&path, contents.as_bytes()) {
+            warn!(%error, path=%path.display(), "failed to write blocklist file");
+        }
+    }
+
+    /// Records that `version` just exited with an unrecognized code, incrementing its consecutive-
+    /// crash count and persisting it. Returns `true` the first time this push crosses
+    /// `self.auto_block_threshold`, signalling to the caller that `version` just became excluded
+    /// from upgrade/downgrade selection by `blocked_versions`, until it either runs cleanly in
+    /// validator mode again (see `record_clean_validator_run`) or is edited out of the blocklist
+    /// file by hand.
+    fn record_crash(&mut self, version: &Version) -> bool {
+        let consecutive_crashes = match self
+            .crash_counts
+            .iter_mut()
+            .find(|count| &count.version == version)
+        {
+            Some(count) => {
+                count.consecutive_crashes += 1;
+                count.consecutive_crashes
+            }
+            None => {
+                self.crash_counts.push(CrashCount {
+                    version: version.clone(),
+                    consecutive_crashes: 1,
+                });
+                1
+            }
+        };
+        self.persist_crash_counts();
+
+        if self.auto_block_threshold > 0 && consecutive_crashes == self.auto_block_threshold {
+            warn!(
+                %version,
+                crashes = consecutive_crashes,
+                "version crashed too many times in a row, auto-blocking it"
+            );
+            return true;
+        }
+        false
+    }
+
+    /// Called right after `blocked_version` has just crossed the auto-block threshold. If a
+    /// non-blocked, lower installed version exists, rolls the launcher back to running it in
+    /// validator mode instead of continuing to retry the now-blocked version, so a crash loop on a
+    /// freshly staged release doesn't wedge the launcher forever. Returns `None` (leaving the
+    /// original crash error to propagate, exactly as before auto-blocking existed) if there's
+    /// nothing lower to fall back to.
+    fn fall_back_from_blocked_version(&mut self, blocked_version: &Version) -> Option<Result<()>> {
+        let fallback_version = self.previous_installed_version(blocked_version).ok()?;
+        if fallback_version >= *blocked_version {
+            return None;
+        }
+
+        warn!(
+            %blocked_version,
+            %fallback_version,
+            "falling back to last-good version after auto-blocking a crash-looping one"
+        );
+        let new_info = self.new_node_info(fallback_version);
+        self.state = State::RunNodeAsValidator(new_info);
+        Some(self.write())
+    }
+
+    /// Resets `version`'s consecutive-crash count, since it just ran cleanly in validator mode. A
+    /// no-op if `version` has no recorded crashes.
+    fn record_clean_validator_run(&mut self, version: &Version) {
+        let had_crashes = self
+            .crash_counts
+            .iter()
+            .any(|count| &count.version == version && count.consecutive_crashes > 0);
+        if had_crashes {
+            self.crash_counts.retain(|count| &count.version != version);
+            self.persist_crash_counts();
+        }
+    }
+
+    /// Returns every version that must be skipped when selecting an upgrade/downgrade target:
+    /// the config-supplied `CASPER_BLOCKED_VERSIONS` set, plus any version whose consecutive-crash
+    /// count has reached `self.auto_block_threshold`. Borrows cargo's yanked-version handling: a
+    /// known-bad release is skipped over during selection rather than uninstalled outright.
+    fn blocked_versions(&self) -> BTreeSet<Version> {
+        let mut blocked = self.configured_blocked_versions.clone();
+        if self.auto_block_threshold > 0 {
+            blocked.extend(
+                self.crash_counts
+                    .iter()
+                    .filter(|count| count.consecutive_crashes >= self.auto_block_threshold)
+                    .map(|count| count.version.clone()),
+            );
+        }
+        blocked
+    }
+
+    /// Gets the most recent installed binary version, skipping any version `blocked_versions`
+    /// excludes as though it weren't installed at all, so a fresh start (no persisted state yet)
+    /// never selects a known-bad release any more than `next_installed_version` would.
+    ///
+    /// Returns an error when no non-blocked version can be detected.
     fn most_recent_version(&self) -> Result<Version> {
+        let blocked = self.blocked_versions();
         let all_versions = utils::versions_from_path(&Self::binary_root_dir())?;
 
-        // We are guaranteed to have at least one version in the `all_versions` container,
-        // because if there are no valid versions installed the `utils::versions_from_path()` bails.
-        Ok(all_versions
+        all_versions
             .into_iter()
-            .last()
-            .expect("must have at least one version"))
+            .rfind(|version| !blocked.contains(version))
+            .ok_or_else(|| anyhow!("every installed version is blocked"))
     }
 
-    /// Gets the next installed version of the node binary and config.
+    /// Gets the next installed version of the node binary and config, skipping any version
+    /// `blocked_versions` excludes as though it weren't installed at all.
     ///
     /// Returns an error if the versions cannot be deduced, or if the two versions are different.
     fn next_installed_version(&self, current_version: &Version) -> Result<Version> {
+        let blocked = self.blocked_versions();
         let next_binary_version =
-            utils::next_installed_version(&self.binary_root_dir, current_version)?;
+            utils::next_installed_version(&self.binary_root_dir, current_version, &blocked)?;
         let next_config_version =
-            utils::next_installed_version(&self.config_root_dir, current_version)?;
+            utils::next_installed_version(&self.config_root_dir, current_version, &blocked)?;
         if next_config_version != next_binary_version {
             warn!(%next_binary_version, %next_config_version, "next version mismatch");
             bail!(
@@ -269,14 +810,16 @@ impl Launcher {
         Ok(next_binary_version)
     }
 
-    /// Gets the previous installed version of the node binary and config.
+    /// Gets the previous installed version of the node binary and config, skipping any version
+    /// `blocked_versions` excludes as though it weren't installed at all.
     ///
     /// Returns an error if the versions cannot be deduced, or if the two versions are different.
     fn previous_installed_version(&self, current_version: &Version) -> Result<Version> {
+        let blocked = self.blocked_versions();
         let previous_binary_version =
-            utils::previous_installed_version(&self.binary_root_dir, current_version)?;
+            utils::previous_installed_version(&self.binary_root_dir, current_version, &blocked)?;
         let previous_config_version =
-            utils::previous_installed_version(&self.config_root_dir, current_version)?;
+            utils::previous_installed_version(&self.config_root_dir, current_version, &blocked)?;
         if previous_config_version != previous_binary_version {
             warn!(%previous_binary_version, %previous_config_version, "previous version mismatch");
             bail!(
@@ -304,6 +847,124 @@ impl Launcher {
         }
     }
 
+    /// Returns the `NodeInfo` the launcher is currently running, or, mid-migration, the one it's
+    /// migrating to.
+    fn active_node_info(&self) -> &NodeInfo {
+        match &self.state {
+            State::RunNodeAsValidator(info) => info,
+            State::MigrateData { new_info, .. } => new_info,
+        }
+    }
+
+    /// Returns every version installed under `binary_root_dir`, ascending.
+    ///
+    /// The result is cached after the first call so `status` and repeated queries within the same
+    /// process don't each re-scan the directory tree.
+    pub fn installed_versions(&self) -> Result<Vec<Version>> {
+        if let Some(cached) = self.installed_versions_cache.borrow().as_ref() {
+            return Ok(cached.clone());
+        }
+
+        let versions: Vec<Version> = utils::versions_from_path(&self.binary_root_dir)?
+            .into_iter()
+            .collect();
+        *self.installed_versions_cache.borrow_mut() = Some(versions.clone());
+        Ok(versions)
+    }
+
+    /// Returns the version the launcher is currently running (or migrating to).
+    pub fn active_version(&self) -> Version {
+        self.active_node_info().version.clone()
+    }
+
+    /// Returns a snapshot of the launcher's current state and the versions available to it.
+    pub fn status(&self) -> Result<Status> {
+        let node_info = self.active_node_info();
+        Ok(Status {
+            active_version: self.active_version(),
+            binary_path: node_info.binary_path.clone(),
+            config_path: node_info.config_path.clone(),
+            migrating: matches!(self.state, State::MigrateData { .. }),
+            installed_versions: self.installed_versions()?,
+        })
+    }
+
+    /// Walks the full ordered sequence of installed versions the launcher would traverse from its
+    /// active version onward (every installed version greater than or equal to it) and checks
+    /// each transition up front, rather than only discovering a problem when `step` reaches it.
+    ///
+    /// Checks, per version in the chain: that it has a matching binary subdir containing
+    /// `casper-node`, that it has a matching config subdir containing `config.toml` (so a
+    /// data-migration target is actually staged before the validator run that precedes it), and
+    /// that the chain is strictly increasing with no duplicates.
+    ///
+    /// This never runs or mutates anything and reports every defect found rather than stopping at
+    /// the first, borrowing OTP relup's "syntax check before install" idea; it backs the
+    /// `--check` CLI mode.
+    pub fn validate_upgrade_path(&self) -> Result<UpgradeValidationReport> {
+        let binary_versions = utils::versions_from_path(&self.binary_root_dir)?;
+        let config_versions = utils::versions_from_path(&self.config_root_dir)?;
+        let current_version = self.active_node_info().version.clone();
+
+        let all_versions: BTreeSet<Version> =
+            binary_versions.union(&config_versions).cloned().collect();
+        let chain: Vec<Version> = all_versions
+            .into_iter()
+            .filter(|version| *version >= current_version)
+            .collect();
+
+        let mut report = UpgradeValidationReport::default();
+        let mut previous: Option<Version> = None;
+        for version in chain {
+            if let Some(previous) = &previous {
+                if version <= *previous {
+                    report.defects.push(UpgradeDefect {
+                        version: version.clone(),
+                        problem: format!(
+                            "version is not strictly greater than the preceding version {} in the chain",
+                            previous
+                        ),
+                    });
+                }
+            }
+
+            let subdir_name = version.to_string().replace('.', "_");
+            if !binary_versions.contains(&version) {
+                report.defects.push(UpgradeDefect {
+                    version: version.clone(),
+                    problem: "has a config subdir but no matching installed binary subdir".to_string(),
+                });
+            } else {
+                let binary_path = self.binary_root_dir.join(&subdir_name).join(NODE_BINARY_NAME);
+                if !binary_path.is_file() {
+                    report.defects.push(UpgradeDefect {
+                        version: version.clone(),
+                        problem: format!("missing node binary at {}", binary_path.display()),
+                    });
+                }
+            }
+
+            if !config_versions.contains(&version) {
+                report.defects.push(UpgradeDefect {
+                    version: version.clone(),
+                    problem: "has a binary subdir but no matching installed config subdir".to_string(),
+                });
+            } else {
+                let config_path = self.config_root_dir.join(&subdir_name).join(NODE_CONFIG_NAME);
+                if !config_path.is_file() {
+                    report.defects.push(UpgradeDefect {
+                        version: version.clone(),
+                        problem: format!("missing node config at {}", config_path.display()),
+                    });
+                }
+            }
+
+            previous = Some(version);
+        }
+
+        Ok(report)
+    }
+
     /// Provides the path to the binary root folder.  casper-node binaries will be installed in a
     /// subdir of this path, where the subdir will be named as per the casper-node version.
     ///
@@ -312,7 +973,7 @@ impl Launcher {
     ///
     /// Otherwise it is `/var/lib/casper/bin`, although this can be overridden (e.g. for external
     /// tests), by setting the env var `CASPER_BIN_DIR` to a different folder.
-    fn binary_root_dir() -> PathBuf {
+    pub(crate) fn binary_root_dir() -> PathBuf {
         #[cfg(not(test))]
         {
             PathBuf::from(match env::var(BINARY_ROOT_DIR_OVERRIDE) {
@@ -339,7 +1000,7 @@ impl Launcher {
     ///
     /// Otherwise it is `/etc/casper`, although this can be overridden (e.g. for external tests), by
     /// setting the env var `CASPER_CONFIG_DIR` to a different folder.
-    fn config_root_dir() -> PathBuf {
+    pub(crate) fn config_root_dir() -> PathBuf {
         #[cfg(not(test))]
         {
             PathBuf::from(match env::var(CONFIG_ROOT_DIR_OVERRIDE) {
@@ -358,11 +1019,31 @@ impl Launcher {
         }
     }
 
+    /// Reads the activation point from the `chainspec.toml` staged alongside `version`'s config,
+    /// if one is present.
+    ///
+    /// Returns `None` (rather than an error) when no chainspec has been staged, so that deployments
+    /// without per-version chainspecs behave exactly as before.
+    fn staged_activation_point(&self, version: &Version) -> Option<utils::ActivationPoint> {
+        let subdir_name = version.to_string().replace('.', "_");
+        let version_dir = self.config_root_dir.join(&subdir_name);
+        utils::read_activation_point(&version_dir).ok()
+    }
+
     /// Sets `self.state` to a new state corresponding to upgrading the current node version.
     ///
     /// If `self.state` is currently `RunNodeAsValidator`, then finds the next installed version
     /// and moves to `MigrateData` if that version is newer (else errors).  If it's currently
     /// `MigrateData`, moves to `RunNodeAsValidator` using the next installed version.
+    ///
+    /// If the next version has a staged chainspec whose timestamp activation point hasn't been
+    /// reached yet, the upgrade is deferred and the launcher keeps re-running the current version
+    /// instead. An era-based activation point is always treated as reached (see
+    /// `utils::activation_point_reached`), so it never defers the upgrade.
+    ///
+    /// The candidate version's binary is verified via `verify::verify_binary` before it's
+    /// committed to `self.state`, so a tampered staged binary is rejected here rather than being
+    /// selected for upgrade and only discovered later, right before `step` spawns it.
     fn upgrade_state(&mut self) -> Result<()> {
         let new_state = match mem::take(&mut self.state) {
             State::RunNodeAsValidator(old_info) => {
@@ -376,8 +1057,21 @@ impl Launcher {
                     bail!(msg);
                 }
 
-                let new_info = self.new_node_info(next_version);
-                State::MigrateData { old_info, new_info }
+                match self.staged_activation_point(&next_version) {
+                    Some(activation_point) if !utils::activation_point_reached(&activation_point) => {
+                        info!(
+                            %next_version,
+                            ?activation_point,
+                            "upgrade pending: activation point not yet reached, continuing on current version"
+                        );
+                        State::RunNodeAsValidator(old_info)
+                    }
+                    _ => {
+                        let new_info = self.new_node_info(next_version);
+                        verify::verify_binary(&new_info.version, &new_info.binary_path)?;
+                        State::MigrateData { old_info, new_info }
+                    }
+                }
             }
             State::MigrateData { new_info, .. } => State::RunNodeAsValidator(new_info),
         };
@@ -390,11 +1084,11 @@ impl Launcher {
     ///
     /// Regardless of the current state variant, the returned state is `RunNodeAsValidator` with the
     /// previous installed version.
+    ///
+    /// As with `upgrade_state`, the candidate version's binary is verified via
+    /// `verify::verify_binary` before it's committed to `self.state`.
     fn downgrade_state(&mut self) -> Result<()> {
-        let node_info = match &self.state {
-            State::RunNodeAsValidator(old_info) => old_info,
-            State::MigrateData { new_info, .. } => new_info,
-        };
+        let node_info = self.active_node_info();
 
         let previous_version = self.previous_installed_version(&node_info.version)?;
         if previous_version >= node_info.version {
@@ -407,13 +1101,72 @@ impl Launcher {
         }
 
         let new_info = self.new_node_info(previous_version);
+        verify::verify_binary(&new_info.version, &new_info.binary_path)?;
         self.state = State::RunNodeAsValidator(new_info);
         Ok(())
     }
 
-    /// Runs the shutdown script if it exists and exits the node-launcher process
-    /// with the exit code returned by the script, otherwise returns 0.
-    fn run_shutdown_script_and_exit(&mut self) -> Result<()> {
+    /// Garbage-collects installed version subdirs (under both `binary_root_dir` and
+    /// `config_root_dir`) beyond the `retention_limit` highest installed versions.  A
+    /// `retention_limit` of `0` means unlimited, so this is a no-op.
+    ///
+    /// Before deleting anything, computes a protected set of versions that are kept regardless of
+    /// the limit: the version `self.state` currently references (by construction, also the version
+    /// the launcher is about to run next, since this runs right after `upgrade_state`), and the
+    /// immediately previous installed version, since `downgrade_state` / `previous_installed_version`
+    /// must still be able to find a valid downgrade target. Each removal deletes the binary subdir
+    /// before the config subdir, so a failure partway through never leaves a binary without its
+    /// config (a failed config removal can leave a config-only subdir behind). Failures enumerating or removing a version are logged and skipped
+    /// rather than propagated, since pruning is a best-effort cleanup, not something that should turn
+    /// a successful upgrade into a failed step.
+    fn prune_old_versions(&self) {
+        if self.retention_limit == 0 {
+            return;
+        }
+
+        let current_version = &self.active_node_info().version;
+
+        let installed: Vec<Version> = match utils::versions_from_path(&self.binary_root_dir) {
+            Ok(versions) => versions.into_iter().collect(),
+            Err(error) => {
+                warn!(%error, "failed to enumerate installed versions for pruning");
+                return;
+            }
+        };
+
+        let previous_version = self.previous_installed_version(current_version).ok();
+
+        let cutoff = installed.len().saturating_sub(self.retention_limit);
+        for version in &installed[..cutoff] {
+            if version == current_version || Some(version) == previous_version.as_ref() {
+                continue;
+            }
+
+            let subdir_name = version.to_string().replace('.', "_");
+            let binary_dir = self.binary_root_dir.join(&subdir_name);
+            let config_dir = self.config_root_dir.join(&subdir_name);
+            if let Err(error) = fs::remove_dir_all(&binary_dir) {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!(%error, path=%binary_dir.display(), "failed to prune old binary dir");
+                    continue;
+                }
+            }
+            if let Err(error) = fs::remove_dir_all(&config_dir) {
+                if error.kind() != io::ErrorKind::NotFound {
+                    warn!(%error, path=%config_dir.display(), "failed to prune old config dir");
+                    continue;
+                }
+            }
+            info!(%version, "pruned old installed version");
+        }
+    }
+
+    /// Runs the shutdown script if it exists and exits the node-launcher process with the exit
+    /// code returned by the script, otherwise returns 0.
+    ///
+    /// Before exiting, records `entry` (with its `shutdown_script_exit_code` filled in) to the
+    /// history file, since in non-test builds `process::exit` never returns to the caller.
+    fn run_shutdown_script_and_exit(&mut self, mut entry: HistoryEntry) -> Result<()> {
         let exit_code = if Path::new(SHUTDOWN_SCRIPT_PATH).exists() {
             info!("running shutdown script at {}.", SHUTDOWN_SCRIPT_PATH);
             let status = utils::map_and_log_error(
@@ -432,6 +1185,9 @@ impl Launcher {
             0
         };
 
+        entry.shutdown_script_exit_code = Some(exit_code);
+        self.append_history(entry);
+
         #[cfg(not(test))]
         process::exit(exit_code);
         #[cfg(test)]
@@ -442,30 +1198,55 @@ impl Launcher {
         }
     }
 
-    /// Moves the launcher state forward.
-    fn transition_state(&mut self, previous_exit_code: NodeExitCode) -> Result<()> {
+    /// Moves the launcher state forward, recording `entry` (minus its `shutdown_script_exit_code`,
+    /// which only `run_shutdown_script_and_exit` can fill in) to the history file.
+    fn transition_state(&mut self, previous_exit_code: NodeExitCode, entry: HistoryEntry) -> Result<()> {
         match previous_exit_code {
-            NodeExitCode::Success => self.upgrade_state()?,
-            NodeExitCode::ShouldDowngrade => self.downgrade_state()?,
-            NodeExitCode::ShouldExitLauncher => self.run_shutdown_script_and_exit()?,
+            NodeExitCode::Success => {
+                self.upgrade_state()?;
+                self.prune_old_versions();
+                self.append_history(entry);
+            }
+            NodeExitCode::ShouldDowngrade => {
+                self.downgrade_state()?;
+                self.append_history(entry);
+            }
+            NodeExitCode::ShouldExitLauncher => self.run_shutdown_script_and_exit(entry)?,
         }
         self.write()
     }
 
     /// Runs the process for the current state and moves the state forward if the process exits with
     /// success.
+    ///
+    /// Before spawning the node binary, verifies its integrity via `verify::verify_binary` (a
+    /// no-op unless `CASPER_VERIFY_BINARIES` is `1` or `true`), so a corrupted, tampered, or unsigned staged
+    /// binary is refused rather than executed.
+    ///
+    /// Records the run to the history file (see `append_history`), classifying it as `Downgrader`
+    /// or `ShutdownScript` rather than `Validator`/`DataMigration` if the exit code triggers one of
+    /// those transitions, since that's the more useful fact for an operator auditing the timeline.
+    ///
+    /// If the node exits with an unrecognized code, records the crash against its version via
+    /// `record_crash`. If that crash was the one that crossed the auto-block threshold,
+    /// `fall_back_from_blocked_version` rolls the launcher back to the last good version instead of
+    /// propagating the error, so a crash loop on a freshly staged release doesn't wedge the
+    /// launcher forever; otherwise (or if there's nothing to fall back to) the error propagates as
+    /// before. Conversely, a validator-mode run that exits successfully resets its version's crash
+    /// count via `record_clean_validator_run`.
     fn step(&mut self) -> Result<()> {
-        let exit_code = match &self.state {
+        let started_at = Utc::now();
+        let (version, run_mode, old_version, command) = match &self.state {
             State::RunNodeAsValidator(node_info) => {
+                verify::verify_binary(&node_info.version, &node_info.binary_path)?;
                 let mut command = Command::new(&node_info.binary_path);
                 command
                     .arg(VALIDATOR_SUBCOMMAND)
                     .arg(&node_info.config_path);
-                let exit_code = utils::run_node(command)?;
-                info!(version=%node_info.version, "finished running node as validator");
-                exit_code
+                (node_info.version.clone(), RunMode::Validator, None, command)
             }
             State::MigrateData { old_info, new_info } => {
+                verify::verify_binary(&new_info.version, &new_info.binary_path)?;
                 let mut command = Command::new(&new_info.binary_path);
                 command
                     .arg(MIGRATE_SUBCOMMAND)
@@ -473,17 +1254,56 @@ impl Launcher {
                     .arg(&old_info.config_path)
                     .arg(NEW_CONFIG_ARG)
                     .arg(&new_info.config_path);
-                let exit_code = utils::run_node(command)?;
-                info!(
-                    old_version=%old_info.version,
-                    new_version=%new_info.version,
-                    "finished data migration"
-                );
-                exit_code
+                (
+                    new_info.version.clone(),
+                    RunMode::DataMigration,
+                    Some(old_info.version.clone()),
+                    command,
+                )
             }
         };
 
-        self.transition_state(exit_code)
+        let exit_code = match utils::run_node(command) {
+            Ok(exit_code) => exit_code,
+            Err(error) => {
+                if error.to_string().ends_with(utils::UNEXPECTED_EXIT_SUFFIX)
+                    && self.record_crash(&version)
+                {
+                    if let Some(result) = self.fall_back_from_blocked_version(&version) {
+                        return result;
+                    }
+                }
+                return Err(error);
+            }
+        };
+        let finished_at = Utc::now();
+
+        match old_version {
+            Some(old_version) => {
+                info!(%old_version, new_version=%version, "finished data migration");
+            }
+            None => info!(%version, "finished running node as validator"),
+        }
+
+        if run_mode == RunMode::Validator && exit_code == NodeExitCode::Success {
+            self.record_clean_validator_run(&version);
+        }
+
+        let mode = match exit_code {
+            NodeExitCode::ShouldDowngrade => RunMode::Downgrader,
+            NodeExitCode::ShouldExitLauncher => RunMode::ShutdownScript,
+            NodeExitCode::Success => run_mode,
+        };
+        let entry = HistoryEntry {
+            version,
+            mode,
+            started_at,
+            finished_at,
+            exit_code,
+            shutdown_script_exit_code: None,
+        };
+
+        self.transition_state(exit_code, entry)
     }
 
     #[cfg(test)]
@@ -511,6 +1331,7 @@ mod tests {
     static V1: Lazy<Version> = Lazy::new(|| Version::new(1, 0, 0));
     static V2: Lazy<Version> = Lazy::new(|| Version::new(2, 0, 0));
     static V3: Lazy<Version> = Lazy::new(|| Version::new(3, 0, 0));
+    static V4: Lazy<Version> = Lazy::new(|| Version::new(4, 0, 0));
 
     /// If `upgrade` is true, installs the new version of the mock node binary, assigning an old
     /// version for the script with the major version of `new_version` decremented by 1.
@@ -704,6 +1525,91 @@ mod tests {
         let error = launcher.step().unwrap_err().to_string();
         assert_last_log_line_eq(&launcher, "Node v3.0.0 ran as validator");
         assert_eq!("no higher version than current 3.0.0 installed", error);
+
+        // The fifth step errored out before reaching `transition_state`'s `append_history` call, so
+        // only the first four successful steps should be recorded, in order, with the expected
+        // versions, modes and exit codes.
+        let history = launcher.history();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[0].version, *V1);
+        assert_eq!(history[0].mode, RunMode::Validator);
+        assert_eq!(history[0].exit_code, NodeExitCode::Success);
+        assert_eq!(history[1].version, *V2);
+        assert_eq!(history[1].mode, RunMode::DataMigration);
+        assert_eq!(history[1].exit_code, NodeExitCode::Success);
+        assert_eq!(history[2].version, *V2);
+        assert_eq!(history[2].mode, RunMode::Validator);
+        assert_eq!(history[2].exit_code, NodeExitCode::Success);
+        assert_eq!(history[3].version, *V3);
+        assert_eq!(history[3].mode, RunMode::DataMigration);
+        assert_eq!(history[3].exit_code, NodeExitCode::Success);
+        for entry in history {
+            assert!(entry.finished_at >= entry.started_at);
+            assert!(entry.shutdown_script_exit_code.is_none());
+        }
+
+        // A fresh launcher constructed from the same config dir should read the persisted history
+        // straight back from disk.
+        let reloaded = Launcher::new(None).unwrap();
+        assert_eq!(reloaded.history(), history);
+    }
+
+    #[test]
+    fn should_prune_old_versions_respecting_retention_limit() {
+        let _ = logging::init();
+
+        // Set up the test folders as if casper-node has just been staged at v4.0.0, but create the
+        // state file first, so that the launcher launches v1.0.0.
+        install_mock(&*V1, NodeExitCode::Success);
+        Launcher::new(None).unwrap();
+        install_mock(&*V2, NodeExitCode::Success);
+        install_mock(&*V3, NodeExitCode::Success);
+        install_mock(&*V4, NodeExitCode::Success);
+
+        let mut launcher = Launcher::new(None).unwrap();
+        launcher.set_retention_limit(2);
+
+        let binary_root_dir = launcher.binary_root_dir.clone();
+        let config_root_dir = launcher.config_root_dir.clone();
+        let version_dir_exists = |version: &Version| {
+            let subdir_name = version.to_string().replace('.', "_");
+            binary_root_dir.join(&subdir_name).exists() || config_root_dir.join(&subdir_name).exists()
+        };
+
+        // Steps 1-2: v1.0.0 validator, then v1.0.0 -> v2.0.0 migration.  v1.0.0 is still the
+        // protected downgrade target throughout, so nothing is pruned yet.
+        launcher.step().unwrap();
+        launcher.step().unwrap();
+        assert!(version_dir_exists(&V1));
+
+        // Step 3: v2.0.0 validator, then starts migrating to v3.0.0.  v1.0.0 is no longer the
+        // current or previous installed version, so it's now outside the retention limit of 2 and
+        // gets pruned.
+        launcher.step().unwrap();
+        assert!(!version_dir_exists(&V1));
+        assert!(version_dir_exists(&V2));
+        assert!(version_dir_exists(&V3));
+        assert!(version_dir_exists(&V4));
+
+        // Step 4: v3.0.0 migration finishes.  v2.0.0 is still the previous installed version, so
+        // it's protected.
+        launcher.step().unwrap();
+        assert!(version_dir_exists(&V2));
+
+        // Step 5: v3.0.0 validator, then starts migrating to v4.0.0.  v2.0.0 is now outside the
+        // retention limit and gets pruned, while v3.0.0 (the previous installed version) stays.
+        launcher.step().unwrap();
+        assert!(!version_dir_exists(&V2));
+        assert!(version_dir_exists(&V3));
+        assert!(version_dir_exists(&V4));
+
+        // Step 6: v4.0.0 migration finishes.
+        launcher.step().unwrap();
+
+        // Step 7: v4.0.0 validator.  No higher version is installed, so the step errors, exactly as
+        // in `should_run_upgrades`.
+        let error = launcher.step().unwrap_err().to_string();
+        assert_eq!("no higher version than current 4.0.0 installed", error);
     }
 
     #[test]
@@ -873,6 +1779,93 @@ mod tests {
         assert_last_log_line_eq(&launcher, "Node v2.0.0 migrated data");
     }
 
+    #[test]
+    fn should_auto_block_perpetually_crashing_version_and_fall_back_to_last_good_version() {
+        let _ = logging::init();
+
+        // Stage v1.0.0 as the only, currently good, installed version.
+        install_mock(&*V1, NodeExitCode::Success);
+        let mut launcher = Launcher::new(None).unwrap();
+        launcher.set_auto_block_threshold(2);
+
+        // Stage v2.0.0, but corrupt its config so every attempt to migrate data to it crashes, and
+        // never fix it, simulating a perpetually-crashing staged release.
+        install_mock(&*V2, NodeExitCode::Success);
+        let node_v2_info = launcher.new_node_info(V2.clone());
+        fs::write(&node_v2_info.config_path, b"bad value").unwrap();
+
+        // Step 1: v1.0.0 validator succeeds, so the launcher starts migrating data to v2.0.0.
+        launcher.step().unwrap();
+        assert_last_log_line_eq(&launcher, "Node v1.0.0 ran as validator");
+
+        // Step 2: the migration to v2.0.0 crashes once (1 of the 2-crash auto-block threshold), so
+        // the error still propagates and the launcher is still trying to migrate to v2.0.0.
+        let error = launcher.step().unwrap_err().to_string();
+        assert!(error.ends_with("exited with error"), "{}", error);
+        assert_eq!(
+            State::MigrateData {
+                old_info: launcher.new_node_info(V1.clone()),
+                new_info: launcher.new_node_info(V2.clone()),
+            },
+            launcher.state
+        );
+
+        // Step 3: the migration to v2.0.0 crashes a second time, crossing the auto-block threshold.
+        // Rather than propagating the error and looping on v2.0.0 forever, the launcher falls back
+        // to running the last-good version, v1.0.0, in validator mode.
+        launcher.step().unwrap();
+        assert_eq!(
+            State::RunNodeAsValidator(launcher.new_node_info(V1.clone())),
+            launcher.state
+        );
+
+        // Step 4: v1.0.0 validator succeeds again, but v2.0.0 is now blocked, so the launcher finds
+        // no non-blocked higher version rather than crash-looping on v2.0.0 again.
+        let error = launcher.step().unwrap_err().to_string();
+        assert_last_log_line_eq(&launcher, "Node v1.0.0 ran as validator");
+        assert_eq!("no higher version than current 1.0.0 installed", error);
+    }
+
+    #[test]
+    fn should_skip_configured_blocked_version_when_upgrading() {
+        let _ = logging::init();
+
+        install_mock(&*V1, NodeExitCode::Success);
+        Launcher::new(None).unwrap();
+        install_mock(&*V2, NodeExitCode::Success);
+        install_mock(&*V3, NodeExitCode::Success);
+
+        let mut launcher = Launcher::new(None).unwrap();
+        launcher.set_blocked_versions([V2.clone()].into_iter().collect());
+
+        // Step 1: v1.0.0 validator succeeds. v2.0.0 is configured as blocked, so the launcher
+        // should skip straight to migrating data to v3.0.0 rather than v2.0.0.
+        launcher.step().unwrap();
+        assert_last_log_line_eq(&launcher, "Node v1.0.0 ran as validator");
+
+        launcher.step().unwrap();
+        assert_last_log_line_eq(&launcher, "Node v3.0.0 migrated data");
+    }
+
+    #[test]
+    fn should_exclude_blocked_version_from_most_recent_version_on_very_first_run() {
+        let _ = logging::init();
+
+        // Stage v1.0.0 and a "broken" v2.0.0, as if casper-node has just been freshly provisioned
+        // on a host which has never run before, i.e. there is no persisted state yet.  Unlike
+        // `should_skip_configured_blocked_version_when_upgrading`, the blocklist is set before
+        // `most_recent_version` is ever consulted, exercising the very-first-run path taken by
+        // `Launcher::new(None)` when no state file exists yet (launcher.rs's `None` branch calls
+        // `most_recent_version` directly, rather than going via `next_installed_version`).
+        install_mock(&*V1, NodeExitCode::Success);
+        install_mock(&*V2, NodeExitCode::Success);
+
+        let mut launcher = Launcher::default();
+        launcher.set_blocked_versions([V2.clone()].into_iter().collect());
+
+        assert_eq!(*V1, launcher.most_recent_version().unwrap());
+    }
+
     #[test]
     fn should_error_if_bin_and_config_have_different_versions() {
         let _ = logging::init();
@@ -910,7 +1903,7 @@ mod tests {
         install_mock(&*V2, NodeExitCode::Success);
         install_mock(&*V3, NodeExitCode::Success);
 
-        let mut launcher = Launcher::new(Some(V2.clone())).unwrap();
+        let mut launcher = Launcher::new(Some(utils::VersionSpec::Exact(V2.clone()))).unwrap();
 
         // Check if forced version is kept in the local state.
         let toml_contents = fs::read_to_string(&launcher.state_path()).unwrap();
@@ -933,7 +1926,9 @@ mod tests {
 
         install_mock(&*V1, NodeExitCode::Success);
 
-        let error = Launcher::new(Some(V2.clone())).unwrap_err().to_string();
+        let error = Launcher::new(Some(utils::VersionSpec::Exact(V2.clone())))
+            .unwrap_err()
+            .to_string();
         assert_eq!(error, "the requested version (2.0.0) is not installed");
     }
 
@@ -984,6 +1979,16 @@ mod tests {
                     .trim_end(),
                 SHUTDOWN_SCRIPT_SUCCESS_OUTPUT
             );
+
+            let history = launcher.history();
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].version, *V1);
+            assert_eq!(history[0].mode, RunMode::ShutdownScript);
+            assert_eq!(history[0].exit_code, NodeExitCode::ShouldExitLauncher);
+            assert_eq!(
+                history[0].shutdown_script_exit_code,
+                Some(SHUTDOWN_SCRIPT_EXIT_CODE)
+            );
         }
 
         // We clean up the test resources to test the case where the script is not present.
@@ -998,6 +2003,60 @@ mod tests {
             launcher.step().expect("should step");
             assert_eq!(launcher.exit_code().unwrap(), 0);
             assert!(!output_path.exists());
+
+            // The history accumulated across both launcher instances in this test, since it's
+            // reloaded from the same config dir each time.
+            let history = launcher.history();
+            assert_eq!(history.len(), 2);
+            assert_eq!(history[1].mode, RunMode::ShutdownScript);
+            assert_eq!(history[1].shutdown_script_exit_code, Some(0));
         }
     }
+
+    #[test]
+    fn should_report_sound_upgrade_path() {
+        let _ = logging::init();
+
+        install_mock(&*V1, NodeExitCode::Success);
+        install_mock(&*V2, NodeExitCode::Success);
+        install_mock(&*V3, NodeExitCode::Success);
+
+        let launcher = Launcher::new(None).unwrap();
+        let report = launcher.validate_upgrade_path().unwrap();
+        assert!(report.is_sound());
+        assert!(report.defects.is_empty());
+    }
+
+    #[test]
+    fn should_report_defects_for_broken_intermediate_version() {
+        let _ = logging::init();
+
+        // Stage v1.0.0 alone first and construct the launcher while everything is still
+        // consistent, since `Launcher::new` bails on any binary/config mismatch; the subsequent
+        // versions are broken only after the launcher already exists.
+        install_mock(&*V1, NodeExitCode::Success);
+        let launcher = Launcher::new(None).unwrap();
+        install_mock(&*V2, NodeExitCode::Success);
+        install_mock(&*V3, NodeExitCode::Success);
+
+        // Break v2.0.0: remove its config subdir entirely, and delete the binary file inside an
+        // otherwise-intact v3.0.0 subdir.
+        let v2_config_dir = Launcher::config_root_dir().join("2_0_0");
+        fs::remove_dir_all(&v2_config_dir).unwrap();
+        let v3_binary_path = Launcher::binary_root_dir().join("3_0_0").join(NODE_BINARY_NAME);
+        fs::remove_file(&v3_binary_path).unwrap();
+
+        let report = launcher.validate_upgrade_path().unwrap();
+        assert!(!report.is_sound());
+
+        assert!(report
+            .defects
+            .iter()
+            .any(|defect| defect.version == *V2
+                && defect.problem.contains("no matching installed config subdir")));
+        assert!(report
+            .defects
+            .iter()
+            .any(|defect| defect.version == *V3 && defect.problem.contains("missing node binary")));
+    }
 }
@@ -1,23 +1,78 @@
 use std::{env, io};
 
 use anyhow::{Error, Result};
-
-use tracing_subscriber::EnvFilter;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::{fmt::MakeWriter, EnvFilter};
 
 const LOG_ENV_VAR: &str = "RUST_LOG";
 const DEFAULT_LOG_LEVEL: &str = "info";
+/// Environment variable selecting the console/file output format: "json" (default), "compact" or
+/// "pretty".
+const LOG_FORMAT_ENV_VAR: &str = "CASPER_LOG_FORMAT";
+const DEFAULT_LOG_FORMAT: &str = "json";
+/// Environment variable giving a directory to additionally write daily-rotated logs to.  Unset
+/// disables the file sink.
+const LOG_DIR_ENV_VAR: &str = "CASPER_LOG_DIR";
+/// The file name prefix used for the rotated log files, e.g. `casper-node-launcher.log.2023-01-01`.
+const LOG_FILE_PREFIX: &str = "casper-node-launcher.log";
 
-pub fn init() -> Result<()> {
+/// Initializes the global tracing subscriber.
+///
+/// The output format defaults to JSON (for log shippers) but can be switched to `compact` or
+/// `pretty` via `CASPER_LOG_FORMAT`, which is friendlier for interactive use where the launcher's
+/// own logs interleave with the child node's output.
+///
+/// If `CASPER_LOG_DIR` is set, logs are written to a daily-rotated file in that directory via a
+/// non-blocking writer instead of stdout.  The returned `WorkerGuard` must be kept alive for the
+/// life of the process (e.g. bound in `main`), or buffered log lines can be lost on exit.
+pub fn init() -> Result<Option<WorkerGuard>> {
     let filter = EnvFilter::new(
         env::var(LOG_ENV_VAR)
             .as_deref()
             .unwrap_or(DEFAULT_LOG_LEVEL),
     );
+    let format = env::var(LOG_FORMAT_ENV_VAR).unwrap_or_else(|_| DEFAULT_LOG_FORMAT.to_string());
+
+    match env::var(LOG_DIR_ENV_VAR) {
+        Ok(log_dir) => {
+            let file_appender = tracing_appender::rolling::daily(log_dir, LOG_FILE_PREFIX);
+            let (non_blocking, guard) = tracing_appender::non_blocking(file_appender);
+            init_with_writer(non_blocking, filter, &format, false)?;
+            Ok(Some(guard))
+        }
+        Err(_) => {
+            init_with_writer(io::stdout, filter, &format, true)?;
+            Ok(None)
+        }
+    }
+}
 
-    Ok(tracing_subscriber::fmt()
-        .with_writer(io::stdout)
-        .with_env_filter(filter)
-        .json()
-        .try_init()
-        .map_err(Error::msg)?)
+/// Builds and installs the subscriber for the given writer and format, emitting ANSI colour codes
+/// in the `compact` and `pretty` formats only when `with_ansi` is `true` (stdout, not the file sink).
+fn init_with_writer<W>(writer: W, filter: EnvFilter, format: &str, with_ansi: bool) -> Result<()>
+where
+    W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
+{
+    match format {
+        "compact" => tracing_subscriber::fmt()
+            .with_writer(writer)
+            .with_env_filter(filter)
+            .with_ansi(with_ansi)
+            .compact()
+            .try_init()
+            .map_err(Error::msg),
+        "pretty" => tracing_subscriber::fmt()
+            .with_writer(writer)
+            .with_env_filter(filter)
+            .with_ansi(with_ansi)
+            .pretty()
+            .try_init()
+            .map_err(Error::msg),
+        _ => tracing_subscriber::fmt()
+            .with_writer(writer)
+            .with_env_filter(filter)
+            .json()
+            .try_init()
+            .map_err(Error::msg),
+    }
 }
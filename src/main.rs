@@ -1,19 +1,24 @@
 #![warn(unused_qualifications)]
+mod config;
+mod install;
 mod launcher;
 mod logging;
 mod utils;
+mod verify;
 
 use std::{
+    io::{self, Write},
     panic::{self, PanicHookInfo},
     str::FromStr,
     sync::{
         atomic::{AtomicU32, Ordering},
-        Arc,
+        Arc, Mutex,
     },
     thread,
+    time::{Duration, Instant},
 };
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use backtrace::Backtrace;
 use clap::{crate_description, crate_version, Arg, Command};
 use nix::{
@@ -22,13 +27,20 @@ use nix::{
 };
 use once_cell::sync::Lazy;
 use semver::Version;
-use signal_hook::{consts::TERM_SIGNALS, iterator::Signals};
-use tracing::warn;
+use signal_hook::{
+    consts::{SIGHUP, TERM_SIGNALS},
+    iterator::Signals,
+};
+use tracing::{info, warn};
 
 use launcher::Launcher;
 
 const APP_NAME: &str = "Casper node launcher";
 
+/// Minimum interval between successive config reloads triggered by `SIGHUP`, coalescing a burst of
+/// signals (e.g. from an editor saving a file in multiple syscalls) into a single reload.
+const RELOAD_DEBOUNCE: Duration = Duration::from_secs(1);
+
 /// Global variable holding the PID of the current child process.
 static CHILD_PID: Lazy<Arc<AtomicU32>> = Lazy::new(|| Arc::new(AtomicU32::new(0)));
 
@@ -62,8 +74,36 @@ fn signal_handler() {
     }
 }
 
+/// Spawns a thread that reloads `node_config` in place every time `SIGHUP` is received, debouncing
+/// rapid successive signals into a single `Config::reload` call. The reloaded config is held only
+/// by this thread: `Launcher` does not read it.
+fn spawn_config_reload_handler(node_config: Arc<Mutex<config::Config>>) {
+    thread::spawn(move || {
+        let mut signals = Signals::new([SIGHUP]).expect("should register SIGHUP handler");
+        let mut last_reload = Instant::now() - RELOAD_DEBOUNCE;
+        for _ in signals.forever() {
+            if last_reload.elapsed() < RELOAD_DEBOUNCE {
+                continue;
+            }
+            last_reload = Instant::now();
+
+            let mut guard = match node_config.lock() {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            match guard.reload() {
+                Ok(true) => info!("reloaded config after SIGHUP"),
+                Ok(false) => warn!("config reload after SIGHUP failed validation, keeping previous config"),
+                Err(error) => warn!(%error, "error reloading config after SIGHUP"),
+            }
+        }
+    });
+}
+
 fn main() -> Result<()> {
-    logging::init()?;
+    // Bound for the life of the process: dropping it would stop the non-blocking file writer (if
+    // any) from flushing further log lines.
+    let _log_guard = logging::init()?;
 
     // Create a panic handler.
     panic::set_hook(Box::new(panic_hook));
@@ -79,19 +119,287 @@ fn main() -> Result<()> {
                 .short('f')
                 .long("force-version")
                 .value_name("version")
-                .help("Forces the launcher to run the specified version of the node, for example \"1.2.3\"")
-                .validator(|arg: &str| Version::from_str(arg).map_err(|_| format!("unable to parse '{arg}' as version")))
+                .help(
+                    "Forces the launcher to run a specific version of the node. Accepts an exact \
+                    version (\"1.2.3\"), a semver requirement (\"^1.5\", \">=1.4, <2\"), or the \
+                    aliases \"latest\"/\"previous\"/\"lowest\", resolved against the installed versions",
+                )
+                .validator(|arg: &str| utils::VersionSpec::from_str(arg).map(|_| ()))
                 .required(false)
                 .takes_value(true),
         )
+        .arg(
+            Arg::new("node-config")
+                .long("node-config")
+                .value_name("path")
+                .help("Overrides the path to the current casper-node config file (or set CASPER_NODE_CONFIG_PATH)")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("print-config")
+                .long("print-config")
+                .help("Prints the resolved node config path and which layer supplied it, then exits"),
+        )
+        .arg(
+            Arg::new("check")
+                .long("check")
+                .help("Validates the whole staged upgrade chain without running anything, then exits"),
+        )
+        .arg(
+            Arg::new("max-retries")
+                .long("max-retries")
+                .value_name("n")
+                .help("Maximum number of times to retry the current version after it exits unexpectedly before giving up")
+                .takes_value(true)
+                .default_value("5"),
+        )
+        .arg(
+            Arg::new("restart-base-delay-secs")
+                .long("restart-base-delay-secs")
+                .value_name("seconds")
+                .help("Base delay before the first retry after an unexpected exit; doubles on each subsequent retry")
+                .takes_value(true)
+                .default_value("1"),
+        )
+        .arg(
+            Arg::new("crash-window-secs")
+                .long("crash-window-secs")
+                .value_name("seconds")
+                .help("Window within which more than `max-retries` unexpected exits are treated as a crash-loop, aborting the launcher")
+                .takes_value(true)
+                .default_value("60"),
+        )
+        .subcommand(
+            Command::new("install")
+                .about("Fetches and stages a casper-node release")
+                .arg(
+                    Arg::new("version")
+                        .value_name("version")
+                        .help(
+                            "The version of casper-node to install: an exact version (\"1.5.2\"), \
+                            a semver requirement (\"^1.5\"), or \"latest\"/\"previous\"/\"lowest\", resolved \
+                            against the remote manifest",
+                        )
+                        .validator(|arg: &str| utils::VersionSpec::from_str(arg).map(|_| ()))
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("base-url")
+                        .long("base-url")
+                        .value_name("url")
+                        .help("Overrides the default release repository base URL (or set CASPER_DIST_URL)")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .help("Overwrites an existing install of the requested version"),
+                ),
+        )
+        .subcommand(
+            Command::new("uninstall")
+                .about("Removes an installed casper-node version")
+                .arg(
+                    Arg::new("version")
+                        .value_name("version")
+                        .help("The installed version to remove, for example \"1.5.2\"")
+                        .validator(|arg: &str| {
+                            Version::from_str(arg).map_err(|_| format!("unable to parse '{arg}' as version"))
+                        })
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("status")
+                .about("Prints the active version, its paths, and all installed versions, then exits")
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .help("Prints the status as JSON instead of a human-readable summary"),
+                ),
+        )
+        .subcommand(
+            Command::new("init")
+                .about("Bootstraps a fresh launcher config at the preferred writable config location, then exits"),
+        )
+        .subcommand(
+            Command::new("prune")
+                .about("Garbage-collects old installed casper-node versions, keeping the highest N")
+                .arg(
+                    Arg::new("keep")
+                        .long("keep")
+                        .value_name("n")
+                        .help("Number of most recent versions to retain")
+                        .takes_value(true)
+                        .default_value("2"),
+                )
+                .arg(
+                    Arg::new("dry-run")
+                        .long("dry-run")
+                        .help("Prints what would be removed without touching the filesystem"),
+                )
+                .arg(
+                    Arg::new("yes")
+                        .long("yes")
+                        .help("Skips the confirmation prompt"),
+                ),
+        )
         .about(crate_description!());
     let matches = command.get_matches();
 
-    // Safe to unwrap() as we have the string validated by `clap` already.
-    let forced_version = matches
+    if let Some(install_matches) = matches.subcommand_matches("install") {
+        // Safe to unwrap() as we have the string validated by `clap` already.
+        let spec = utils::VersionSpec::from_str(install_matches.value_of("version").unwrap()).unwrap();
+        let base_url = install_matches
+            .value_of("base-url")
+            .map(String::from)
+            .unwrap_or_else(install::dist_url);
+        let force = install_matches.is_present("force");
+        let installed = install::install_version_spec(
+            &base_url,
+            &Launcher::binary_root_dir(),
+            &Launcher::config_root_dir(),
+            &spec,
+            force,
+        )?;
+        println!("installed {}", installed);
+        return Ok(());
+    }
+
+    if let Some(uninstall_matches) = matches.subcommand_matches("uninstall") {
+        // Safe to unwrap() as we have the string validated by `clap` already.
+        let version = Version::from_str(uninstall_matches.value_of("version").unwrap()).unwrap();
+        return install::uninstall_version(
+            &Launcher::binary_root_dir(),
+            &Launcher::config_root_dir(),
+            &version,
+        );
+    }
+
+    if matches.subcommand_matches("init").is_some() {
+        let config = config::Config::init()?;
+        println!(
+            "wrote config at {} (node config: {})",
+            config::Config::locate()?.display(),
+            config.node_config_path().display()
+        );
+        return Ok(());
+    }
+
+    if let Some(prune_matches) = matches.subcommand_matches("prune") {
+        let keep: usize = prune_matches
+            .value_of("keep")
+            .unwrap()
+            .parse()
+            .unwrap_or(2);
+        let dry_run = prune_matches.is_present("dry-run");
+        let skip_confirmation = prune_matches.is_present("yes");
+
+        let binary_root_dir = Launcher::binary_root_dir();
+        let config_root_dir = Launcher::config_root_dir();
+        let retained_version = launcher::current_version(&config_root_dir);
+        let to_prune = install::versions_to_prune(&binary_root_dir, keep, retained_version.as_ref())?;
+
+        if to_prune.is_empty() {
+            println!("nothing to prune");
+            return Ok(());
+        }
+
+        println!("would remove: {}", utils::iter_to_string(&to_prune));
+        if dry_run {
+            return Ok(());
+        }
+
+        if !skip_confirmation {
+            print!("remove {} version(s) listed above? [y/N] ", to_prune.len());
+            io::stdout().flush()?;
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !answer.trim().eq_ignore_ascii_case("y") {
+                println!("aborted");
+                return Ok(());
+            }
+        }
+
+        for version in &to_prune {
+            install::uninstall_version(&binary_root_dir, &config_root_dir, version)?;
+        }
+        return Ok(());
+    }
+
+    if let Some(status_matches) = matches.subcommand_matches("status") {
+        let launcher = Launcher::new(None)?;
+        let status = launcher.status()?;
+        if status_matches.is_present("json") {
+            println!("{}", serde_json::to_string_pretty(&status)?);
+        } else {
+            println!("active version: {}", status.active_version);
+            println!("migrating: {}", status.migrating);
+            println!("binary path: {}", status.binary_path.display());
+            println!("config path: {}", status.config_path.display());
+            println!(
+                "installed versions: {}",
+                utils::iter_to_string(&status.installed_versions)
+            );
+        }
+        return Ok(());
+    }
+
+    if matches.is_present("check") {
+        let launcher = Launcher::new(None)?;
+        let report = launcher.validate_upgrade_path()?;
+        if report.is_sound() {
+            println!("upgrade path is sound: no defects found");
+            return Ok(());
+        }
+        for defect in &report.defects {
+            println!("{}: {}", defect.version, defect.problem);
+        }
+        bail!(
+            "upgrade path validation found {} defect(s)",
+            report.defects.len()
+        );
+    }
+
+    if matches.is_present("print-config") {
+        let (resolved, source) =
+            config::Config::new_with_provenance(matches.value_of("node-config"))?;
+        println!("node_config_path = {}", resolved.node_config_path().display());
+        println!("source = {:?}", source);
+        return Ok(());
+    }
+
+    // Safe to unwrap() as we have the string validated by `clap` already.  Resolution against the
+    // installed versions happens inside `Launcher::new`, which has already enumerated them itself.
+    let forced_version_spec = matches
         .value_of("force-version")
-        .map(|ver| Version::from_str(ver).unwrap());
+        .map(|spec| utils::VersionSpec::from_str(spec).unwrap());
+
+    let restart_policy = launcher::RestartPolicy {
+        max_retries: matches.value_of("max-retries").unwrap().parse().unwrap_or(5),
+        base_delay: Duration::from_secs(
+            matches
+                .value_of("restart-base-delay-secs")
+                .unwrap()
+                .parse()
+                .unwrap_or(1),
+        ),
+        crash_window: Duration::from_secs(
+            matches
+                .value_of("crash-window-secs")
+                .unwrap()
+                .parse()
+                .unwrap_or(60),
+        ),
+    };
+
+    // The launcher's own config is optional: if it can't be resolved (e.g. no node config staged
+    // yet), there's nothing to hot-reload, so just skip the watcher rather than failing the run.
+    if let Ok(node_config) = config::Config::new(matches.value_of("node-config")) {
+        spawn_config_reload_handler(Arc::new(Mutex::new(node_config)));
+    }
 
-    let mut launcher = Launcher::new(forced_version)?;
+    let mut launcher = Launcher::new(forced_version_spec)?;
+    launcher.set_restart_policy(restart_policy);
     launcher.run()
 }
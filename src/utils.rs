@@ -3,12 +3,17 @@ use std::{
     sync::atomic::Ordering,
 };
 
-use anyhow::{bail, Error, Result};
-use semver::Version;
+use anyhow::{anyhow, bail, Error, Result};
+use chrono::{DateTime, Utc};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 
+/// The name of the chainspec file staged alongside a version's node config.
+const CHAINSPEC_FILE_NAME: &str = "chainspec.toml";
+
 /// Represents the exit code of the node process.
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Debug)]
 #[repr(i32)]
 pub(crate) enum NodeExitCode {
     /// Indicates a successful execution.
@@ -20,18 +25,23 @@ pub(crate) enum NodeExitCode {
 }
 
 /// Iterates the given path, returning the subdir representing the immediate next SemVer version
-/// after `current_version`.
+/// after `current_version`, skipping over any version in `blocked` as though it weren't installed
+/// at all, so a known-bad staged release is never picked as an upgrade target.
 ///
 /// Subdir names should be semvers with dots replaced with underscores.
 pub(crate) fn next_installed_version<P: AsRef<Path>>(
     dir: P,
     current_version: &Version,
+    blocked: &BTreeSet<Version>,
 ) -> Result<Version> {
     let max_version = Version::new(u64::max_value(), u64::max_value(), u64::max_value());
 
     let mut next_version = max_version.clone();
     for installed_version in versions_from_path(dir)? {
-        if installed_version > *current_version && installed_version < next_version {
+        if installed_version > *current_version
+            && installed_version < next_version
+            && !blocked.contains(&installed_version)
+        {
             next_version = installed_version;
         }
     }
@@ -44,18 +54,23 @@ pub(crate) fn next_installed_version<P: AsRef<Path>>(
 }
 
 /// Iterates the given path, returning the subdir representing the immediate previous SemVer version
-/// before `current_version`.
+/// before `current_version`, skipping over any version in `blocked` as though it weren't installed
+/// at all, so a known-bad staged release is never picked as a downgrade target.
 ///
 /// Subdir names should be semvers with dots replaced with underscores.
 pub(crate) fn previous_installed_version<P: AsRef<Path>>(
     dir: P,
     current_version: &Version,
+    blocked: &BTreeSet<Version>,
 ) -> Result<Version> {
     let min_version = Version::new(0, 0, 0);
 
     let mut previous_version = min_version.clone();
     for installed_version in versions_from_path(dir)? {
-        if installed_version < *current_version && installed_version > previous_version {
+        if installed_version < *current_version
+            && installed_version > previous_version
+            && !blocked.contains(&installed_version)
+        {
             previous_version = installed_version;
         }
     }
@@ -109,6 +124,153 @@ pub(crate) fn versions_from_path<P: AsRef<Path>>(dir: P) -> Result<BTreeSet<Vers
     Ok(versions)
 }
 
+/// A specification of a node version, as accepted by the `--force-version` CLI argument.
+///
+/// This is a grammar of ways to pick a version out of the versions already installed: an exact
+/// version, a semver requirement such as `^1.5` or `>=1.4, <2`, or the symbolic aliases `latest`,
+/// `previous` and `lowest`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum VersionSpec {
+    Exact(Version),
+    Req(VersionReq),
+    Latest,
+    Previous,
+    Lowest,
+}
+
+impl FromStr for VersionSpec {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value {
+            "latest" => return Ok(VersionSpec::Latest),
+            "previous" => return Ok(VersionSpec::Previous),
+            "lowest" => return Ok(VersionSpec::Lowest),
+            _ => {}
+        }
+        if let Ok(version) = Version::from_str(value) {
+            return Ok(VersionSpec::Exact(version));
+        }
+        VersionReq::from_str(value).map(VersionSpec::Req).map_err(|_| {
+            format!(
+                "unable to parse '{value}' as a version, version requirement, 'latest', \
+                'previous' or 'lowest'"
+            )
+        })
+    }
+}
+
+/// Resolves `spec` against an already-collected set of installed versions, returning the concrete
+/// `Version` to run.
+///
+/// Resolution failures (no installed version matches the requirement) produce a clear error
+/// rather than silently falling back to the currently running version.
+pub(crate) fn resolve_version_spec_from_installed(
+    installed: BTreeSet<Version>,
+    spec: &VersionSpec,
+) -> Result<Version> {
+    match spec {
+        VersionSpec::Exact(version) => {
+            if installed.contains(version) {
+                Ok(version.clone())
+            } else {
+                bail!("the requested version ({}) is not installed", version)
+            }
+        }
+        VersionSpec::Latest => Ok(installed
+            .into_iter()
+            .next_back()
+            .expect("must have at least one version")),
+        VersionSpec::Previous => {
+            let mut installed_descending = installed.into_iter().rev();
+            let _latest = installed_descending
+                .next()
+                .expect("must have at least one version");
+            installed_descending
+                .next()
+                .ok_or_else(|| anyhow!("no previous installed version besides the latest"))
+        }
+        VersionSpec::Lowest => Ok(installed
+            .into_iter()
+            .next()
+            .expect("must have at least one version")),
+        VersionSpec::Req(req) => installed
+            .into_iter()
+            .rev()
+            .find(|version| req.matches(version))
+            .ok_or_else(|| anyhow!("no installed version satisfies requirement '{}'", req)),
+    }
+}
+
+/// The point at which a staged protocol version is scheduled to activate, as read from that
+/// version's `chainspec.toml`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ActivationPoint {
+    /// Activates once the chain reaches the given era.
+    EraId(u64),
+    /// Activates at the given genesis timestamp, for brand new networks.
+    Genesis(DateTime<Utc>),
+}
+
+/// Reads `<version_dir>/chainspec.toml` and returns its `protocol.activation_point`.
+pub(crate) fn read_activation_point<P: AsRef<Path>>(version_dir: P) -> Result<ActivationPoint> {
+    let path = version_dir.as_ref().join(CHAINSPEC_FILE_NAME);
+    let contents = map_and_log_error(
+        fs::read_to_string(&path),
+        format!("failed to read {}", path.display()),
+    )?;
+    let chainspec: toml::Value = map_and_log_error(
+        toml::from_str(&contents),
+        format!("failed to parse {}", path.display()),
+    )?;
+
+    let activation_point = chainspec
+        .get("protocol")
+        .and_then(|protocol| protocol.get("activation_point"))
+        .ok_or_else(|| anyhow!("{} has no [protocol] activation_point", path.display()))?;
+
+    match activation_point {
+        toml::Value::Integer(era) => Ok(ActivationPoint::EraId(*era as u64)),
+        toml::Value::String(timestamp) => {
+            let parsed = DateTime::parse_from_rfc3339(timestamp)
+                .map_err(|error| anyhow!("{} has an invalid activation_point timestamp: {}", path.display(), error))?;
+            Ok(ActivationPoint::Genesis(parsed.with_timezone(&Utc)))
+        }
+        _ => bail!(
+            "{} has an activation_point of an unexpected type",
+            path.display()
+        ),
+    }
+}
+
+/// Returns whether `activation_point` has already been reached.
+///
+/// A genesis-style timestamp point is compared against wall-clock time. An era-based point is
+/// NOT actually verified: the launcher has no feed of the chain's current era (only the node's
+/// consensus component tracks that), so this unconditionally reports it as reached rather than
+/// deferring the upgrade. This means the "avoid prematurely starting a version whose activation
+/// point hasn't arrived" protection this function exists for does not apply to era-gated staged
+/// versions today — the node itself is relied on to refuse to activate early. Verifying era-based
+/// points properly needs an era source wired into the launcher, which is a separate piece of work.
+pub(crate) fn activation_point_reached(activation_point: &ActivationPoint) -> bool {
+    match activation_point {
+        ActivationPoint::EraId(era) => {
+            warn!(
+                era,
+                "era-based activation point cannot be verified by the launcher (no era feed \
+                available); proceeding as if reached and deferring to the node to enforce it"
+            );
+            true
+        }
+        ActivationPoint::Genesis(timestamp) => Utc::now() >= *timestamp,
+    }
+}
+
+/// The fixed suffix of the error message produced when a node process exits with a code other
+/// than the recognized `NodeExitCode` values.  Exposed so callers (e.g. the launcher's restart
+/// policy) can recognize this specific failure without re-parsing the whole message.
+pub(crate) const UNEXPECTED_EXIT_SUFFIX: &str = "exited with error";
+
 /// Runs the given command as a child process.
 pub(crate) fn run_node(mut command: Command) -> Result<NodeExitCode> {
     let mut child = map_and_log_error(command.spawn(), format!("failed to execute {:?}", command))?;
@@ -136,7 +298,7 @@ pub(crate) fn run_node(mut command: Command) -> Result<NodeExitCode> {
         }
         _ => {
             warn!(%exit_status, "failed running {:?}", command);
-            bail!("{:?} exited with error", command);
+            bail!("{:?} {}", command, UNEXPECTED_EXIT_SUFFIX);
         }
     }
 }
@@ -186,7 +348,7 @@ mod tests {
         let tempdir = tempfile::tempdir().expect("should create temp dir");
 
         let get_next_version = |current_version: &Version| {
-            next_installed_version(tempdir.path(), current_version).unwrap()
+            next_installed_version(tempdir.path(), current_version, &BTreeSet::new()).unwrap()
         };
 
         let mut current = Version::new(0, 0, 0);
@@ -208,6 +370,40 @@ mod tests {
         assert_eq!(get_next_version(&current), Version::new(2, 2, 2));
     }
 
+    #[test]
+    fn should_skip_blocked_versions_when_getting_next_installed_version() {
+        let _ = logging::init();
+        let tempdir = tempfile::tempdir().expect("should create temp dir");
+
+        fs::create_dir(tempdir.path().join("2_0_0")).unwrap();
+        fs::create_dir(tempdir.path().join("3_0_0")).unwrap();
+        fs::create_dir(tempdir.path().join("4_0_0")).unwrap();
+
+        let current_version = Version::new(1, 0, 0);
+        let blocked: BTreeSet<Version> = [Version::new(2, 0, 0), Version::new(3, 0, 0)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            next_installed_version(tempdir.path(), &current_version, &blocked).unwrap(),
+            Version::new(4, 0, 0)
+        );
+
+        // With every installed higher version blocked, falls back to reporting `current_version`,
+        // exactly as when no higher version is installed at all.
+        let blocked: BTreeSet<Version> = [
+            Version::new(2, 0, 0),
+            Version::new(3, 0, 0),
+            Version::new(4, 0, 0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            next_installed_version(tempdir.path(), &current_version, &blocked).unwrap(),
+            current_version
+        );
+    }
+
     #[test]
     fn should_ignore_invalid_versions() {
         let _ = logging::init();
@@ -216,7 +412,7 @@ mod tests {
 
         // Try with a non-existent dir.
         let non_existent_dir = Path::new("not_a_dir");
-        let error = next_installed_version(non_existent_dir, &current_version)
+        let error = next_installed_version(non_existent_dir, &current_version, &BTreeSet::new())
             .unwrap_err()
             .to_string();
         assert_eq!(
@@ -225,7 +421,7 @@ mod tests {
         );
 
         // Try with a dir which has no subdirs.
-        let error = next_installed_version(tempdir.path(), &current_version)
+        let error = next_installed_version(tempdir.path(), &current_version, &BTreeSet::new())
             .unwrap_err()
             .to_string();
         assert_eq!(
@@ -238,7 +434,7 @@ mod tests {
 
         // Try with a dir which has one subdir which is not a valid version representation.
         fs::create_dir(tempdir.path().join("not_a_version")).unwrap();
-        let error = next_installed_version(tempdir.path(), &current_version)
+        let error = next_installed_version(tempdir.path(), &current_version, &BTreeSet::new())
             .unwrap_err()
             .to_string();
         assert_eq!(
@@ -252,7 +448,7 @@ mod tests {
         // Try with a dir which has a valid and invalid subdir - the invalid one should be ignored.
         fs::create_dir(tempdir.path().join("1_2_3")).unwrap();
         assert_eq!(
-            next_installed_version(tempdir.path(), &current_version).unwrap(),
+            next_installed_version(tempdir.path(), &current_version, &BTreeSet::new()).unwrap(),
             Version::new(1, 2, 3)
         );
     }
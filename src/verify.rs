@@ -0,0 +1,226 @@
+//! Opt-in integrity verification of staged `casper-node` binaries before they're executed.
+//!
+//! Verification is off by default so existing deployments that don't publish sidecar
+//! checksums/signatures alongside their binaries are unaffected; set `CASPER_VERIFY_BINARIES=1`
+//! to require it.
+
+use std::{
+    env,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use semver::Version;
+use sha2::{Digest, Sha256};
+
+/// Environment variable gating binary verification before launch.
+const VERIFY_BINARIES_ENV: &str = "CASPER_VERIFY_BINARIES";
+/// Environment variable giving the trusted Ed25519 public key(s) used to check `.sig` sidecars,
+/// as a `:`-separated list of paths (mirroring `PATH`). A signature is accepted if it verifies
+/// against any one of them, so a key can be rotated by adding the new one ahead of removing the
+/// old. If unset, verification falls back to a `.sha256` checksum sidecar instead.
+const PUBKEY_PATH_ENV: &str = "CASPER_PUBKEY_PATH";
+/// Extension of the checksum sidecar expected alongside a staged binary.
+const CHECKSUM_EXTENSION: &str = "sha256";
+/// Extension of the detached signature sidecar expected alongside a staged binary.
+const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Returns `true` if `CASPER_VERIFY_BINARIES` requests verification of staged binaries.
+fn verification_enabled() -> bool {
+    matches!(
+        env::var(VERIFY_BINARIES_ENV).as_deref(),
+        Ok("1") | Ok("true")
+    )
+}
+
+/// Returns the configured trusted public key paths, if `CASPER_PUBKEY_PATH` is set.
+fn trusted_pubkey_paths() -> Option<Vec<PathBuf>> {
+    let raw = env::var_os(PUBKEY_PATH_ENV)?;
+    Some(env::split_paths(&raw).collect())
+}
+
+/// Verifies the integrity of `version`'s binary at `binary_path` before it's launched or selected
+/// as an upgrade/downgrade target, if `CASPER_VERIFY_BINARIES` is `1` or `true`; otherwise a no-op.
+///
+/// If `CASPER_PUBKEY_PATH` is also set, a detached Ed25519 signature sidecar (`<binary>.sig`) is
+/// required and checked against the trusted keys it lists. Otherwise a `<binary>.sha256` checksum
+/// sidecar is required and compared against the binary's own digest. Either check failing, or the
+/// relevant sidecar being missing, bails with a clear error rather than running an unverified
+/// binary.
+pub(crate) fn verify_binary(version: &Version, binary_path: &Path) -> Result<()> {
+    if !verification_enabled() {
+        return Ok(());
+    }
+
+    match trusted_pubkey_paths() {
+        Some(pubkey_paths) => verify_signature(version, binary_path, &pubkey_paths),
+        None => verify_checksum(binary_path),
+    }
+}
+
+/// Returns the sidecar path for `binary_path`, e.g. `casper-node` -> `casper-node.sha256`.
+fn sidecar_path(binary_path: &Path, extension: &str) -> PathBuf {
+    let mut file_name = binary_path
+        .file_name()
+        .unwrap_or_default()
+        .to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    binary_path.with_file_name(file_name)
+}
+
+fn verify_checksum(binary_path: &Path) -> Result<()> {
+    let sidecar_path = sidecar_path(binary_path, CHECKSUM_EXTENSION);
+    let expected = fs::read_to_string(&sidecar_path).with_context(|| {
+        format!(
+            "binary verification is enabled but no checksum sidecar was found at {}",
+            sidecar_path.display()
+        )
+    })?;
+    let expected = expected
+        .split_whitespace()
+        .next()
+        .with_context(|| format!("{} is empty", sidecar_path.display()))?
+        .to_lowercase();
+
+    let mut file = File::open(binary_path)
+        .with_context(|| format!("failed to open {}", binary_path.display()))?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    let actual = hex::encode(hasher.finalize());
+
+    if actual != expected {
+        bail!(
+            "checksum mismatch for {}: expected {}, got {}",
+            binary_path.display(),
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Loads a trusted key from `pubkey_path`, which must contain the raw 32-byte Ed25519 public key.
+fn load_trusted_key(pubkey_path: &Path) -> Result<VerifyingKey> {
+    let pubkey_bytes = fs::read(pubkey_path)
+        .with_context(|| format!("failed to read public key at {}", pubkey_path.display()))?;
+    let pubkey_bytes: [u8; 32] = pubkey_bytes.try_into().map_err(|_| {
+        anyhow!(
+            "{} is not a 32-byte Ed25519 public key",
+            pubkey_path.display()
+        )
+    })?;
+    VerifyingKey::from_bytes(&pubkey_bytes).with_context(|| {
+        format!(
+            "{} is not a valid Ed25519 public key",
+            pubkey_path.display()
+        )
+    })
+}
+
+/// Checks `binary_path`'s detached `.sig` sidecar against `trusted_pubkey_paths`, accepting it if
+/// it verifies against any one of the trusted keys.
+fn verify_signature(
+    version: &Version,
+    binary_path: &Path,
+    trusted_pubkey_paths: &[PathBuf],
+) -> Result<()> {
+    let sidecar_path = sidecar_path(binary_path, SIGNATURE_EXTENSION);
+    let signature_bytes = fs::read(&sidecar_path).with_context(|| {
+        format!(
+            "binary verification is enabled but no signature sidecar was found at {}",
+            sidecar_path.display()
+        )
+    })?;
+    let signature = Signature::from_slice(&signature_bytes).with_context(|| {
+        format!("{} is not a valid Ed25519 signature", sidecar_path.display())
+    })?;
+
+    let binary_bytes = fs::read(binary_path)
+        .with_context(|| format!("failed to read {}", binary_path.display()))?;
+
+    let trusted_keys = trusted_pubkey_paths
+        .iter()
+        .map(|path| load_trusted_key(path))
+        .collect::<Result<Vec<_>>>()?;
+
+    let verified = trusted_keys
+        .iter()
+        .any(|key| key.verify(&binary_bytes, &signature).is_ok());
+
+    if !verified {
+        bail!("signature verification failed for {}", version);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    use super::*;
+
+    const SEED: [u8; 32] = [7u8; 32];
+
+    /// Writes `contents` as a binary under `dir`, signs it with a fixed test key, and writes both
+    /// the `.sig` sidecar and the trusted public key to disk. Returns their paths.
+    fn write_signed_binary(dir: &Path, contents: &[u8]) -> (PathBuf, PathBuf) {
+        let binary_path = dir.join("casper-node");
+        fs::write(&binary_path, contents).unwrap();
+
+        let signing_key = SigningKey::from_bytes(&SEED);
+        let signature = signing_key.sign(contents);
+        fs::write(sidecar_path(&binary_path, SIGNATURE_EXTENSION), signature.to_bytes()).unwrap();
+
+        let pubkey_path = dir.join("trusted.pub");
+        fs::write(&pubkey_path, signing_key.verifying_key().to_bytes()).unwrap();
+
+        (binary_path, pubkey_path)
+    }
+
+    #[test]
+    fn should_verify_binary_with_valid_signature() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (binary_path, pubkey_path) =
+            write_signed_binary(temp_dir.path(), b"trustworthy binary");
+
+        verify_signature(&Version::new(1, 0, 0), &binary_path, &[pubkey_path]).unwrap();
+    }
+
+    #[test]
+    fn should_reject_tampered_binary() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (binary_path, pubkey_path) =
+            write_signed_binary(temp_dir.path(), b"trustworthy binary");
+
+        // Tamper with the binary after it was signed; the signature sidecar still matches the
+        // original contents, so this must be rejected.
+        fs::write(&binary_path, b"tampered binary").unwrap();
+
+        let error = verify_signature(&Version::new(1, 2, 3), &binary_path, &[pubkey_path])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "signature verification failed for 1.2.3");
+    }
+
+    #[test]
+    fn should_reject_missing_signature_sidecar() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let binary_path = temp_dir.path().join("casper-node");
+        fs::write(&binary_path, b"unsigned binary").unwrap();
+        let pubkey_path = temp_dir.path().join("trusted.pub");
+        fs::write(
+            &pubkey_path,
+            SigningKey::from_bytes(&SEED).verifying_key().to_bytes(),
+        )
+        .unwrap();
+
+        let error = verify_signature(&Version::new(1, 0, 0), &binary_path, &[pubkey_path])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("no signature sidecar was found"));
+    }
+}